@@ -175,6 +175,18 @@ mod test {
         assert_eq!(list.pop(), Some(7));
         assert_eq!(list.pop(), None);
     }
+
+    #[test]
+    fn linked_list_macro() {
+        let mut list = linked_list![fifth => 1, 2, 3];
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+
+        let list = linked_list![fifth => 'a'; 2];
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), &['a', 'a']);
+    }
 }
 #[test]
 fn miri_food() {
@@ -103,25 +103,25 @@ impl<T> List<T> {
         })
     }
 
-    pub fn peek_front(&self) -> Option<Ref<T>> {
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
         self.head.as_ref().map(|node| {
             Ref::map(node.borrow(), |node| &node.elem)
         })
     }
 
-    pub fn peek_back(&self) -> Option<Ref<T>> {
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
         self.tail.as_ref().map(|node| {
             Ref::map(node.borrow(), |node| &node.elem)
         })
     }
 
-    pub fn peek_front_mut(&self) -> Option<RefMut<T>> {
+    pub fn peek_front_mut(&self) -> Option<RefMut<'_, T>> {
         self.head.as_ref().map(|node| {
             RefMut::map(node.borrow_mut(), |node| &mut node.elem)
         })
     }
 
-    pub fn peek_back_mut(&self) -> Option<RefMut<T>> {
+    pub fn peek_back_mut(&self) -> Option<RefMut<'_, T>> {
         self.tail.as_ref().map(|node| {
             RefMut::map(node.borrow_mut(), |node| &mut node.elem)
         })
@@ -235,6 +235,18 @@ mod test {
         assert_eq!(&*list.peek_back_mut().unwrap(), &mut 3);
     }
 
+    #[test]
+    fn linked_list_macro() {
+        let mut list = linked_list![fourth => 1, 2, 3];
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+
+        let list = linked_list![fourth => 0; 3];
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), &[0, 0, 0]);
+    }
+
     #[test]
     fn into_iter() {
         let mut list = List::new();
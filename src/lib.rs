@@ -1,6 +1,26 @@
+#[macro_use]
+mod macros;
+
+// The book's lists are kept as the book wrote them, so these lints are
+// silenced here instead of by rewriting them.
+#[allow(dead_code, clippy::redundant_field_names, clippy::new_without_default)]
 pub mod first;
 pub mod second;
+#[allow(clippy::redundant_field_names, clippy::new_without_default)]
 pub mod third;
+#[allow(
+    clippy::redundant_field_names,
+    clippy::new_without_default,
+    clippy::should_implement_trait
+)]
 pub mod fourth;
+#[allow(
+    unused_mut,
+    clippy::redundant_field_names,
+    clippy::new_without_default,
+    clippy::should_implement_trait,
+    clippy::redundant_pattern_matching,
+    clippy::option_map_unit_fn
+)]
 pub mod fifth;
 pub mod sixth;
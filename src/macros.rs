@@ -0,0 +1,86 @@
+/// Builds a list from its elements, like `vec!`.
+///
+/// On its own this makes a `sixth::LinkedList`. Prefix the elements with a
+/// module name and `=>` to build that module's `List` instead. Elements are
+/// pushed in the order they're written, using that list's own push, so the
+/// stacks (`second` and `third`) come out with the last element on top.
+///
+/// ```
+/// use many_linked_lists::linked_list;
+///
+/// let list = linked_list![1, 2, 3];
+/// assert_eq!(list, [1, 2, 3]);
+///
+/// let zeros = linked_list![0; 4];
+/// assert_eq!(zeros, [0, 0, 0, 0]);
+///
+/// let mut stack = linked_list![second => 1, 2, 3];
+/// assert_eq!(stack.pop(), Some(3));
+///
+/// let mut queue = linked_list![fifth => 1, 2, 3];
+/// assert_eq!(queue.pop(), Some(1));
+/// ```
+#[macro_export]
+macro_rules! linked_list {
+    (second => $elem:expr; $n:expr) => {{
+        let mut list = $crate::second::List::new();
+        let elem = $elem;
+        for _ in 0..$n {
+            list.push(::std::clone::Clone::clone(&elem));
+        }
+        list
+    }};
+    (second => $($x:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut list = $crate::second::List::new();
+        $(list.push($x);)*
+        list
+    }};
+    (third => $elem:expr; $n:expr) => {{
+        let mut list = $crate::third::List::new();
+        let elem = $elem;
+        for _ in 0..$n {
+            list = list.prepend(::std::clone::Clone::clone(&elem));
+        }
+        list
+    }};
+    (third => $($x:expr),* $(,)?) => {
+        $crate::third::List::new()$(.prepend($x))*
+    };
+    (fourth => $elem:expr; $n:expr) => {{
+        let mut list = $crate::fourth::List::new();
+        let elem = $elem;
+        for _ in 0..$n {
+            list.push_back(::std::clone::Clone::clone(&elem));
+        }
+        list
+    }};
+    (fourth => $($x:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut list = $crate::fourth::List::new();
+        $(list.push_back($x);)*
+        list
+    }};
+    (fifth => $elem:expr; $n:expr) => {{
+        let mut list = $crate::fifth::List::new();
+        let elem = $elem;
+        for _ in 0..$n {
+            list.push(::std::clone::Clone::clone(&elem));
+        }
+        list
+    }};
+    (fifth => $($x:expr),* $(,)?) => {{
+        #[allow(unused_mut)]
+        let mut list = $crate::fifth::List::new();
+        $(list.push($x);)*
+        list
+    }};
+    ($elem:expr; $n:expr) => {
+        ::std::iter::repeat($elem)
+            .take($n)
+            .collect::<$crate::sixth::LinkedList<_>>()
+    };
+    ($($x:expr),* $(,)?) => {
+        $crate::sixth::LinkedList::from([$($x),*])
+    };
+}
@@ -1,8 +1,12 @@
-use std::mem;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::hash::{Hash, Hasher};
+use std::iter::{FromIterator, FusedIterator};
+use std::mem::MaybeUninit;
 
 pub struct List<T> {
     head: Link<T>,
-
+    len: usize,
 }
 
 type Link<T> = Option<Box<Node<T>>>;
@@ -12,41 +16,229 @@ struct Node<T> {
     next: Link<T>,
 }
 
+/// A stash of spare node allocations that `push_with` draws from and
+/// `pop_into` hands back, so a busy stack can stop talking to the allocator.
+pub struct NodePool<T> {
+    // uninit: the elem has been moved out (or never written) and `next` is
+    // meaningless, so these must never be dropped as real Nodes
+    free: Vec<Box<MaybeUninit<Node<T>>>>,
+}
+
+impl<T> NodePool<T> {
+    pub fn new() -> Self {
+        NodePool { free: Vec::new() }
+    }
+
+    /// Number of spare nodes in the pool.
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+
+    /// Makes sure at least `n` spare nodes are ready to go.
+    pub fn reserve(&mut self, n: usize) {
+        let extra = n.saturating_sub(self.free.len());
+        self.free.reserve(extra);
+        for _ in 0..extra {
+            self.free.push(Box::new_uninit());
+        }
+    }
+
+    /// Gives every spare node back to the allocator.
+    pub fn shrink(&mut self) {
+        self.free = Vec::new();
+    }
+
+    fn alloc(&mut self, node: Node<T>) -> Box<Node<T>> {
+        let mut slot = self.free.pop().unwrap_or_else(Box::new_uninit);
+        slot.write(node);
+        // SAFETY: we just wrote a whole Node into it
+        unsafe { slot.assume_init() }
+    }
+
+    fn recycle(&mut self, node: Box<Node<T>>) -> T {
+        let raw = Box::into_raw(node);
+        // SAFETY: `raw` came from a live Box, so it's valid to read. Once the
+        // elem is moved out the allocation is only handed around as
+        // MaybeUninit, which never drops its contents. Callers detach `next`
+        // first so we don't leak the rest of a chain.
+        unsafe {
+            let elem = std::ptr::read(&(*raw).elem);
+            self.free.push(Box::from_raw(raw.cast::<MaybeUninit<Node<T>>>()));
+            elem
+        }
+    }
+}
+
+impl<T> Default for NodePool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for NodePool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NodePool").field("len", &self.len()).finish()
+    }
+}
+
 impl<T> List<T> {
     pub fn new() -> Self {
-        List { head: None }
+        List { head: None, len: 0 }
     }
 
     pub fn push(&mut self, elem: T) {
         let new_node: Box<Node<T>> = Box::new(Node {
-            elem: elem,
+            elem,
             next: self.head.take(),
         });
 
         self.head = Some(new_node);
+        self.len += 1;
     }
 
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
             self.head = node.next;
+            self.len -= 1;
             node.elem
         })
     }
 
+    /// Like `push`, but takes the node from `pool` if it has one spare.
+    pub fn push_with(&mut self, elem: T, pool: &mut NodePool<T>) {
+        let node = pool.alloc(Node { elem, next: None });
+        self.push_node(node);
+    }
+
+    /// Like `pop`, but keeps the freed node in `pool` for a later `push_with`.
+    pub fn pop_into(&mut self, pool: &mut NodePool<T>) -> Option<T> {
+        self.pop_node().map(|node| pool.recycle(node))
+    }
+
+    fn push_node(&mut self, mut node: Box<Node<T>>) {
+        node.next = self.head.take();
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    // Detaches the head node without reallocating it.
     fn pop_node(&mut self) -> Link<T> {
-        self.head.take().map(|node| {
-            self.head = node.next;
-            Box::new(Node{elem: node.elem, next: None})
+        self.head.take().map(|mut node| {
+            self.head = node.next.take();
+            self.len -= 1;
+            node
         })
     }
 
-    fn peek(&self) -> Option<&T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn reverse(&mut self) {
+        // pop nodes off the old chain and push them onto the new one,
+        // relinking the boxes as we go
+        let mut reversed: Link<T> = None;
+        let mut cur = self.head.take();
+        while let Some(mut node) = cur {
+            cur = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    // Other's chain goes after ours, so `append` undoes `split_off`. With no
+    // tail pointer that means walking our whole chain to find the end.
+    pub fn append(&mut self, other: &mut Self) {
+        let mut tail = &mut self.head;
+        while let Some(node) = tail {
+            tail = &mut node.next;
+        }
+        *tail = other.head.take();
+        self.len += std::mem::replace(&mut other.len, 0);
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b))
+    }
+
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+
+    // Sorts head to tail, so the smallest element ends up on top.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        // Bottom-up merge sort. bins[i] is either empty or a sorted run of
+        // 2^i nodes, so 64 of them covers any list that fits in memory and
+        // lives on the stack. Every node is always in one of the guard's
+        // lists, which go back into us when it drops, even if `compare`
+        // panics halfway through a merge.
+        let rest = std::mem::take(self);
+        let mut g = SortGuard {
+            list: self,
+            bins: std::array::from_fn(|_| List::new()),
+            carry: List::new(),
+            merged: List::new(),
+            rest,
+        };
+
+        while let Some(mut node) = g.rest.head.take() {
+            g.rest.head = node.next.take();
+            g.rest.len -= 1;
+            g.carry = List { head: Some(node), len: 1 };
+            // like incrementing a binary counter: merge up until we hit an
+            // empty bin. Lower bins always hold the later nodes, so they go
+            // second to keep things stable.
+            let mut i = 0;
+            while g.bins[i].head.is_some() {
+                merge(&mut g.bins[i], &mut g.carry, &mut g.merged, &mut compare);
+                std::mem::swap(&mut g.carry, &mut g.merged);
+                i += 1;
+            }
+            std::mem::swap(&mut g.bins[i], &mut g.carry);
+        }
+
+        for i in 0..g.bins.len() {
+            if g.bins[i].head.is_some() {
+                merge(&mut g.bins[i], &mut g.carry, &mut g.merged, &mut compare);
+                std::mem::swap(&mut g.carry, &mut g.merged);
+            }
+        }
+        // dropping the guard hands the sorted run in `carry` back to us
+    }
+
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        let mut link = &mut self.head;
+        for _ in 0..at {
+            link = &mut link.as_mut().unwrap().next;
+        }
+        let rest = List {
+            head: link.take(),
+            len: self.len - at,
+        };
+        self.len = at;
+        rest
+    }
+
+    pub fn peek(&self) -> Option<&T> {
         self.head.as_ref().map(|node| {
             &node.elem
         })
     }
 
-    fn peek_mut(&mut self) -> Option<&mut T> {
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
         self.head.as_mut().map(|node| {
             &mut node.elem
         })
@@ -56,8 +248,11 @@ impl<T> List<T> {
 
 pub struct IntoIter<T>(List<T>);
 
-impl<T> List<T> {
-    fn into_iter(self) -> IntoIter<T> {
+impl<T> IntoIterator for List<T> {
+    type IntoIter = IntoIter<T>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
         IntoIter(self)
     }
 }
@@ -80,7 +275,7 @@ impl<T> List<T> {
     // We declare a fresh lifetime here fore the _exact_ borrow that
     //  creates the iter. Now &self needs to be valid as long as the
     //  Iter is around.
-    fn iter<'a>(&'a self) -> Iter<'a, T> {
+    pub fn iter<'a>(&'a self) -> Iter<'a, T> {
         // `as_deref` is essentially `.map(|node| &**node)`
         // Rust normally does _deref coercion_ where it inserts
         // those *'s throughout your code to make it type check
@@ -113,19 +308,82 @@ impl<'a, T> Iterator for Iter<'a, T> {
             // This lets the compiler know that `&node` should have a
             // deref coercion applied to it, so we don't need to
             // manually apply **
-            self.next = node.next.as_ref().map::<&Node<T>, _>(|node| &node);
+            self.next = node.next.as_ref().map::<&Node<T>, _>(|node| node);
 
             &node.elem
         })
     }
 }
 
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Holds a list's nodes while `sort_by` works on them, and links whatever
+/// it's holding back into the list when dropped.
+struct SortGuard<'a, T> {
+    list: &'a mut List<T>,
+    bins: [List<T>; 64],
+    // the run being carried up through the bins, and the merge in progress
+    carry: List<T>,
+    merged: List<T>,
+    // nodes we haven't got to yet
+    rest: List<T>,
+}
+
+impl<T> Drop for SortGuard<'_, T> {
+    fn drop(&mut self) {
+        // After a clean sort only `carry` has anything in it. After a panic
+        // the pieces go back in whatever order they're in.
+        let parts = std::iter::once(&mut self.merged)
+            .chain(std::iter::once(&mut self.carry))
+            .chain(self.bins.iter_mut())
+            .chain(std::iter::once(&mut self.rest));
+        for part in parts {
+            if part.head.is_some() {
+                if self.list.head.is_some() {
+                    // only walks `part`, so putting it all back stays linear
+                    part.append(self.list);
+                }
+                std::mem::swap(self.list, part);
+            }
+        }
+    }
+}
+
+/// Moves two sorted runs into the empty `out` by relinking their boxes,
+/// taking from `a` on ties. Each node is always in exactly one of the three.
+fn merge<T, F>(a: &mut List<T>, b: &mut List<T>, out: &mut List<T>, compare: &mut F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut tail = &mut out.head;
+    while let (Some(x), Some(y)) = (&a.head, &b.head) {
+        let src = if compare(&x.elem, &y.elem) != Ordering::Greater {
+            &mut *a
+        } else {
+            &mut *b
+        };
+        let mut node = src.head.take().unwrap();
+        src.head = node.next.take();
+        src.len -= 1;
+        tail = &mut tail.insert(node).next;
+        out.len += 1;
+    }
+    // one side ran out, the rest of the other is already in order
+    let src = if a.head.is_some() { a } else { b };
+    *tail = src.head.take();
+    out.len += std::mem::replace(&mut src.len, 0);
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         let mut cur_link = self.head.take();
         // Do this until pattern doesn't match
         while let Some(mut boxed_node) = cur_link {
-            cur_link = mem::replace(&mut boxed_node.next, None);
+            cur_link = boxed_node.next.take();
             // boxed_node goes out of scope and gets dropped here
             // but it's Node's `next` field that has been set to Link::Empty
             // so no unbounded recursion occurrs.
@@ -133,6 +391,15 @@ impl<T> Drop for List<T> {
     }
 }
 
+impl<'a, T> IntoIterator for &'a List<T> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
 }
@@ -155,9 +422,141 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     }
 }
 
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Lazily unlinks and yields the elements matching a predicate. Whatever the
+/// iterator hasn't reached when it's dropped stays in the list.
+pub struct DrainFilter<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    // the link we look at next, None once we've walked off the end
+    cur: Option<&'a mut Link<T>>,
+    len: &'a mut usize,
+    pred: F,
+}
+
+impl<T> List<T> {
+    pub fn drain_filter<F>(&mut self, pred: F) -> DrainFilter<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        DrainFilter {
+            cur: Some(&mut self.head),
+            len: &mut self.len,
+            pred,
+        }
+    }
+
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.drain_filter(|elem| !f(elem)).for_each(drop);
+    }
+
+    /// Removes and returns the first element (from the top) matching `pred`.
+    pub fn remove_first<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> Option<T> {
+        self.drain_filter(|elem| pred(elem)).next()
+    }
+}
+
+impl<'a, T, F> Iterator for DrainFilter<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(link) = self.cur.take() {
+            let matched = match link.as_mut() {
+                Some(node) => (self.pred)(&mut node.elem),
+                None => return None,
+            };
+            if matched {
+                // splice the node out and stay on the same link, which now
+                // holds its successor
+                let mut node = link.take().unwrap();
+                *link = node.next.take();
+                *self.len -= 1;
+                self.cur = Some(link);
+                return Some(node.elem);
+            }
+            self.cur = Some(&mut link.as_mut().unwrap().next);
+        }
+        None
+    }
+}
+
+impl<T, F> FusedIterator for DrainFilter<'_, T, F> where F: FnMut(&mut T) -> bool {}
+
+// None of these can be derived: the derives recurse down the Box<Node>
+// chain, which blows the stack on long lists just like the default Drop.
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        let mut new_list = List::new();
+        // keep a handle on the empty link at the end, and fill it in as we go
+        let mut tail = &mut new_list.head;
+        for elem in self {
+            let node = tail.insert(Box::new(Node {
+                elem: elem.clone(),
+                next: None,
+            }));
+            tail = &mut node.next;
+        }
+        new_list.len = self.len;
+        new_list
+    }
+}
+
+impl<T: Debug> Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // hash the length too, so [a, b] and [a][b] don't run together
+        self.len.hash(state);
+        for elem in self {
+            elem.hash(state);
+        }
+    }
+}
+
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    // it's a stack, so the last element in ends up on top
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::List;
+    use super::{List, Node, NodePool};
 
     #[test]
     fn basics() {
@@ -182,6 +581,21 @@ mod tests {
     }
 
     #[test]
+    fn linked_list_macro() {
+        let mut list = linked_list![second => 1, 2, 3];
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+
+        let mut list = linked_list![second => "x"; 2];
+        assert_eq!(list.pop(), Some("x"));
+        assert_eq!(list.pop(), Some("x"));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    #[allow(clippy::option_map_unit_fn)]
     fn peek() {
         let mut list = List::new();
         assert_eq!(list.peek(), None);
@@ -233,6 +647,323 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn into_iterator_refs() {
+        let mut list: List<i32> = (1..=3).collect();
+        for elem in &mut list {
+            *elem *= 10;
+        }
+        let mut seen = Vec::new();
+        for elem in &list {
+            seen.push(*elem);
+        }
+        assert_eq!(seen, &[30, 20, 10]);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), &[30, 20, 10]);
+    }
+
+    #[test]
+    fn traits() {
+        use std::collections::HashSet;
+
+        let mut list: List<i32> = List::default();
+        assert_eq!(format!("{:?}", list), "[]");
+        list.extend([1, 2, 3]);
+        assert_eq!(format!("{:?}", list), "[3, 2, 1]");
+
+        let copy = list.clone();
+        assert_eq!(copy, list);
+        assert_eq!(copy.iter().collect::<Vec<_>>(), list.iter().collect::<Vec<_>>());
+
+        let other: List<i32> = [1, 2].into_iter().collect();
+        assert!(other != list);
+        assert!(List::<i32>::new() != list);
+
+        let mut set = HashSet::new();
+        assert!(set.insert(list.clone()));
+        assert!(!set.insert(copy));
+        assert!(set.insert(other));
+    }
+
+    #[test]
+    fn len() {
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+        list.push(1); list.push(2);
+        assert_eq!(list.len(), 2);
+        list.pop();
+        assert_eq!(list.len(), 1);
+        list.pop();
+        list.pop();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        let list: List<i32> = (0..5).collect();
+        assert_eq!(list.clone().len(), 5);
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list: List<i32> = List::new();
+        list.reverse();
+        assert!(list.is_empty());
+
+        list.push(1); list.push(2); list.push(3);
+        list.reverse();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn append_split_off() {
+        let mut list: List<i32> = (1..=3).collect();
+        let mut other: List<i32> = (4..=5).collect();
+        list.append(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[3, 2, 1, 5, 4]);
+
+        let mut empty = List::new();
+        empty.append(&mut list);
+        assert_eq!(empty.len(), 5);
+        assert!(list.is_empty());
+
+        let mut list = empty;
+        for at in 0..=5 {
+            let mut copy = list.clone();
+            let mut rest = copy.split_off(at);
+            assert_eq!(copy.len(), at);
+            assert_eq!(rest.len(), 5 - at);
+            assert_eq!(copy.iter().count(), at);
+            assert_eq!(rest.iter().count(), 5 - at);
+            copy.append(&mut rest);
+            assert_eq!(copy, list);
+        }
+
+        let rest = list.split_off(2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[3, 2]);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), &[1, 5, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut list: List<i32> = (1..=3).collect();
+        list.split_off(4);
+    }
+
+    #[test]
+    fn sort() {
+        let mut list: List<i32> = List::new();
+        list.sort();
+        assert!(list.is_empty());
+
+        let mut list: List<i32> = [5, 1, 4, 1, 5, 9, 2, 6, 5, 3].into_iter().collect();
+        list.sort();
+        assert_eq!(list.len(), 10);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[1, 1, 2, 3, 4, 5, 5, 5, 6, 9]);
+
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(list.pop(), Some(9));
+        assert_eq!(list.pop(), Some(6));
+
+        // sort_by_key is stable: ties keep their order from the top down
+        let mut list: List<(u8, char)> = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e')]
+            .into_iter()
+            .rev()
+            .collect();
+        list.sort_by_key(|&(key, _)| key);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            &[(0, 'e'), (1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]
+        );
+    }
+
+    #[test]
+    fn sort_panic_leaves_list_usable() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        // blow up at every point in the sort, every element has to survive
+        for limit in 0.. {
+            let tracker = Rc::new(());
+            let mut list: List<(i32, Rc<()>)> =
+                (0..20).map(|x| (x * 7 % 20, Rc::clone(&tracker))).collect();
+            let mut calls = 0;
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                list.sort_by(|a, b| {
+                    calls += 1;
+                    if calls > limit {
+                        panic!("bad comparator");
+                    }
+                    a.0.cmp(&b.0)
+                });
+            }));
+            assert_eq!(list.len(), 20);
+            let mut elems: Vec<i32> = list.iter().map(|&(x, _)| x).collect();
+            elems.sort();
+            assert_eq!(elems, (0..20).collect::<Vec<_>>());
+
+            list.push((20, Rc::clone(&tracker)));
+            assert_eq!(list.len(), 21);
+            drop(list);
+            assert_eq!(Rc::strong_count(&tracker), 1);
+            if result.is_ok() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn sort_long_list() {
+        // no recursion, so a long chain is fine
+        let mut list: List<u64> = (0..1_000_000).map(|x| x * 7919 % 1_000_003).collect();
+        list.sort();
+        assert_eq!(list.len(), 1_000_000);
+        let sorted: Vec<u64> = list.iter().copied().collect();
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn node_pool() {
+        let mut pool = NodePool::new();
+        pool.reserve(4);
+        assert_eq!(pool.len(), 4);
+        pool.reserve(2);
+        assert_eq!(pool.len(), 4);
+
+        let mut list = List::new();
+        list.push_with(1, &mut pool);
+        list.push_with(2, &mut pool);
+        list.push(3);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(list.len(), 3);
+
+        // recycled nodes come straight back out of the pool
+        let first = list.head.as_deref().map(|node| node as *const Node<_>);
+        assert_eq!(list.pop_into(&mut pool), Some(3));
+        assert_eq!(pool.len(), 3);
+        list.push_with(4, &mut pool);
+        assert_eq!(list.head.as_deref().map(|node| node as *const Node<_>), first);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[4, 2, 1]);
+
+        // pooled nodes work with everything else too
+        list.push_with(5, &mut pool);
+        list.push_with(6, &mut pool);
+        list.push_with(7, &mut pool);
+        assert!(pool.is_empty());
+        list.sort();
+        assert_eq!(list.pop_into(&mut pool), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop_into(&mut pool), Some(4));
+        assert_eq!(pool.len(), 2);
+
+        pool.shrink();
+        assert!(pool.is_empty());
+        assert_eq!(list.pop_into(&mut pool), Some(5));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn node_pool_drops() {
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        let mut pool = NodePool::new();
+        {
+            let mut list = List::new();
+            for _ in 0..10 {
+                list.push_with(Rc::clone(&tracker), &mut pool);
+            }
+            for _ in 0..5 {
+                list.pop_into(&mut pool);
+            }
+            assert_eq!(Rc::strong_count(&tracker), 6);
+        }
+        // the rest went with the list, the pool holds no elements
+        assert_eq!(Rc::strong_count(&tracker), 1);
+        assert_eq!(pool.len(), 5);
+    }
+
+    #[test]
+    fn retain() {
+        let mut list: List<i32> = (1..=10).rev().collect();
+        list.retain(|&x| x % 3 != 0);
+        assert_eq!(list.len(), 7);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[1, 2, 4, 5, 7, 8, 10]);
+
+        list.retain(|_| true);
+        assert_eq!(list.len(), 7);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn remove_first() {
+        let mut list: List<i32> = [1, 2, 3, 2, 1].into_iter().collect();
+        assert_eq!(list.remove_first(|&x| x == 2), Some(2));
+        assert_eq!(list.remove_first(|&x| x == 9), None);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[1, 3, 2, 1]);
+
+        assert_eq!(list.remove_first(|&x| x == 1), Some(1));
+        assert_eq!(list.remove_first(|&x| x == 1), Some(1));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[3, 2]);
+
+        list.push(4);
+        assert_eq!(list.pop(), Some(4));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn drain_filter() {
+        let mut list: List<i32> = (1..=8).rev().collect();
+        let evens: Vec<_> = list.drain_filter(|x| *x % 2 == 0).collect();
+        assert_eq!(evens, &[2, 4, 6, 8]);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[1, 3, 5, 7]);
+
+        // the predicate can edit what it keeps
+        let big: Vec<_> = list
+            .drain_filter(|x| {
+                *x *= 10;
+                *x > 40
+            })
+            .collect();
+        assert_eq!(big, &[50, 70]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[10, 30]);
+
+        // lazy: stopping early leaves the rest alone
+        let mut list: List<i32> = (1..=6).rev().collect();
+        {
+            let mut iter = list.drain_filter(|_| true);
+            assert_eq!(iter.next(), Some(1));
+            assert_eq!(iter.next(), Some(2));
+        }
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), &[3, 4, 5, 6]);
+
+        let mut iter = list.drain_filter(|_| false);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn long_list_clone_and_eq() {
+        // these would overflow the stack if they recursed down the chain
+        let list: List<u32> = (0..1_000_000).collect();
+        let copy = list.clone();
+        assert!(list == copy);
+        assert_eq!(copy.peek(), Some(&999_999));
+    }
+
     #[test]
     fn iter_mut() {
         let mut list = List::new();
@@ -1,14 +1,24 @@
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{self, HashMap, VecDeque};
 use std::fmt::{self, Debug};
-use std::hash::{Hash, Hasher};
-use std::iter::FromIterator;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::iter::{FromIterator, FusedIterator};
+use std::ops::{Bound, Index, IndexMut, RangeBounds};
 use std::ptr::NonNull;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
 
 pub struct LinkedList<T> {
     front: Link<T>,
     back: Link<T>,
     len: usize,
+    /// The generation each live NodeHandle into us has to match, keyed by
+    /// its node. Stays empty (and unallocated) until we hand out a handle.
+    handles: HandleMap<T>,
+    /// Handles from before this generation were all killed at once. Their
+    /// entries in `handles` get swept out later, by `make_handle`.
+    handles_from: u64,
     /// We semantically store values of T by-value
     _boo: PhantomData<T>,
 }
@@ -21,6 +31,31 @@ struct Node<T> {
     elem: T,
 }
 
+// Keys are our own node pointers, so a randomly seeded hasher buys nothing
+type HandleMap<T> = HashMap<NonNull<Node<T>>, u64, BuildHasherDefault<DefaultHasher>>;
+
+/// Points at a node in O(1) without borrowing the list. The list only hands
+/// the node back while it's still listed under the handle's generation, so
+/// we never have to look at freed memory to reject a handle.
+///
+/// A handle is good while its node stays in the list it came from. Removing
+/// the node kills it. Splitting nodes off in bulk (`split_off`, the cursor
+/// splits, `split_into_chunks`) kills every handle into the list, since
+/// finding the ones that left would mean walking them; `truncate`, `drain`
+/// and `remove_range` walk what they remove anyway, so they only kill those.
+/// Nodes joining from another list (`append`, `prepend`, the splices) leave
+/// their handles behind. None of this ever touches the nodes themselves.
+pub struct NodeHandle<T> {
+    node: NonNull<Node<T>>,
+    generation: u64,
+}
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, AtomicOrdering::Relaxed)
+}
+
 pub struct Iter<'a, T> {
     front: Link<T>,
     back: Link<T>,
@@ -39,9 +74,50 @@ pub struct IntoIter<T> {
     list: LinkedList<T>,
 }
 
+pub struct Pairs<'a, T> {
+    // first node of the front pair, and second node of the back pair
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+/// Hands out two neighbours at once. It can't be an Iterator, since each
+/// pair shares a node with the next, so the borrow has to end first.
+pub struct PairsMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+pub struct Drain<'a, T> {
+    // the range is already cut out of the list, we just own it now
+    iter: IntoIter<T>,
+    _boo: PhantomData<&'a mut LinkedList<T>>,
+}
+
+pub struct ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    cursor: CursorMut<'a, T>,
+    pred: F,
+}
+
+pub struct Cursor<'a, T> {
+    cur: Link<T>,
+    list: &'a LinkedList<T>,
+    /// None when we're on the ghost, but also when `cur` is a node we were
+    /// made at from a NodeHandle and nobody has asked where that is yet.
+    /// `LinkedList::cursor_index` tells the two apart.
+    index: Option<usize>,
+}
+
 pub struct CursorMut<'a, T> {
     cur: Link<T>,
     list: &'a mut LinkedList<T>,
+    /// Same two meanings of None as `Cursor::index`.
     index: Option<usize>,
 }
 
@@ -51,6 +127,8 @@ impl<T> LinkedList<T> {
             front: None,
             back: None,
             len: 0,
+            handles: HandleMap::default(),
+            handles_from: 0,
             _boo: PhantomData,
         }
     }
@@ -108,6 +186,7 @@ impl<T> LinkedList<T> {
             // because everything is Copy and there are no dtors that will
             // run if we mess up... riiight?
             self.front.map(|node| {
+                self.forget_node(node);
                 // Bring the Box back to life so we can move out its value and
                 // Drop it (Box continues to magically understand this for us)
                 let boxed_node = Box::from_raw(node.as_ptr());
@@ -138,6 +217,7 @@ impl<T> LinkedList<T> {
             // because everything is Copy and there are no dtors that will
             // run if we mess up... riiight?
             self.back.map(|node| {
+                self.forget_node(node);
                 // Bring the Box back to life so we can move out its value and
                 // Drop it (Box continues to magically understand this for us)
                 let boxed_node = Box::from_raw(node.as_ptr());
@@ -170,948 +250,3797 @@ impl<T> LinkedList<T> {
     }
 
     pub fn clear(&mut self) {
-        while let Some(_) = self.pop_front() { }
+        while self.pop_front().is_some() { }
     }
 
-    pub fn front(&self) -> Option<&T> {
-        unsafe {
-            Some(&(*self.front?.as_ptr()).elem)
-        }
+    pub fn append(&mut self, other: &mut Self) {
+        self.link_back(other);
+        // other's handles would now point into us, so they're done
+        other.forget_all();
     }
 
-    pub fn back(&self) -> Option<&T> {
-        unsafe {
-            Some(&(*self.back?.as_ptr()).elem)
-        }
+    pub fn prepend(&mut self, other: &mut Self) {
+        let mut ours = self.take_nodes();
+        self.link_back(other);
+        self.link_back(&mut ours);
+        other.forget_all();
     }
 
-    pub fn front_mut(&mut self) -> Option<&mut T> {
-        unsafe {
-            self.front.map(|node| &mut (*node.as_ptr()).elem)
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "Cannot split off at a nonexistent index");
+        let rest = self.detach_from(at);
+        if !rest.is_empty() {
+            self.forget_all();
         }
+        rest
     }
 
-    pub fn back_mut(&mut self) -> Option<&mut T> {
-        unsafe {
-            self.back.map(|node| &mut (*node.as_ptr()).elem)
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            let rest = self.detach_from(len);
+            self.forget_nodes(&rest);
         }
     }
 
-    pub fn iter(&self) -> Iter<T> {
-        Iter {
-            front: self.front,
-            back: self.back,
-            len: self.len,
-            _boo: PhantomData,
+    pub fn truncate_front(&mut self, len: usize) {
+        if len < self.len {
+            let front = self.detach_to(self.len - len);
+            self.forget_nodes(&front);
         }
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<T> {
-        IterMut {
-            front: self.front,
-            back: self.back,
-            len: self.len,
-            _boo: PhantomData,
+    pub fn partition<F: FnMut(&T) -> bool>(mut self, mut pred: F) -> (Self, Self) {
+        // pull matching nodes out one at a time and hang them off `matched`,
+        // whatever is left in self is the rest, still in order
+        let mut matched = LinkedList::new();
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        while let Some(elem) = cursor.current() {
+            if pred(elem) {
+                let mut node = cursor.remove_current_as_list().unwrap();
+                matched.append(&mut node);
+            } else {
+                cursor.move_next();
+            }
         }
+        (matched, self)
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter {
-            list: self
+    pub fn split_into_chunks(mut self, n: usize) -> Vec<Self> {
+        assert!(n != 0, "chunk size must be non-zero");
+        let mut chunks = Vec::with_capacity(self.len.div_ceil(n));
+        while !self.is_empty() {
+            let rest = self.split_off(n.min(self.len));
+            chunks.push(std::mem::replace(&mut self, rest));
         }
+        chunks
     }
 
-    pub fn cursor_mut(&mut self) -> CursorMut<T> {
-        CursorMut {
-            list: self,
-            cur: None,
-            index: None,
+    pub fn reverse(&mut self) {
+        unsafe {
+            // every node just trades its neighbours, no values move
+            let mut cur = self.front;
+            while let Some(node) = cur {
+                let node = &mut *node.as_ptr();
+                std::mem::swap(&mut node.front, &mut node.back);
+                // what was `back` is now `front`
+                cur = node.front;
+            }
+        }
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    pub fn rotate_left(&mut self, n: usize) {
+        assert!(n <= self.len, "rotation amount (is {}) should be <= len (is {})", n, self.len);
+        // finding the split walks from the nearer end, the rest is O(1)
+        // relinking. No node leaves us, so neither does any handle.
+        if n != 0 && n != self.len {
+            let mut back = self.detach_from(n);
+            let mut front = self.take_nodes();
+            self.link_back(&mut back);
+            self.link_back(&mut front);
         }
     }
-}
 
-impl<T> Drop for LinkedList<T> {
-    fn drop(&mut self) {
-        // Pop until we have to stop
-        while let Some(_) = self.pop_front() { }
+    pub fn rotate_right(&mut self, n: usize) {
+        assert!(n <= self.len, "rotation amount (is {}) should be <= len (is {})", n, self.len);
+        self.rotate_left(self.len - n);
     }
-}
-
-impl<'a, T> IntoIterator for &'a LinkedList<T> {
-    type IntoIter = Iter<'a, T>;
-    type Item = &'a T;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    pub fn get(&self, at: usize) -> Option<&T> {
+        unsafe {
+            self.node_at(at).map(|node| &(*node.as_ptr()).elem)
+        }
     }
-}
-
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // While self.front == self.back is a tempting condition to check here,
-        // it won't do the right for yielding the last element! That sort of
-        // thing only works for arrays because of "one-past-the-end" pointers.
-        if self.len > 0 {
-            // We could unwrap front, but this is safer and easier
-            self.front.map(|node| unsafe {
-                self.len -= 1;
-                self.front = (*node.as_ptr()).back;
-                &(*node.as_ptr()).elem
-            })
-        } else {
-            None
+    pub fn get_mut(&mut self, at: usize) -> Option<&mut T> {
+        unsafe {
+            self.node_at(at).map(|node| &mut (*node.as_ptr()).elem)
         }
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    pub fn insert(&mut self, at: usize, elem: T) {
+        let len = self.len;
+        if let Err(_elem) = self.try_insert(at, elem) {
+            panic!("insertion index (is {}) should be <= len (is {})", at, len);
+        }
     }
-}
 
-impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.len > 0 {
-            self.back.map(|node| unsafe {
-                self.len -= 1;
-                self.back = (*node.as_ptr()).front;
-                &(*node.as_ptr()).elem
-            })
+    pub fn try_insert(&mut self, at: usize, elem: T) -> Result<(), T> {
+        if at > self.len {
+            // hand the element back rather than dropping it
+            return Err(elem);
+        }
+        if at == self.len {
+            self.push_back(elem);
         } else {
-            None
+            let cur = self.node_at(at);
+            let mut cursor = CursorMut {
+                cur,
+                list: self,
+                index: Some(at),
+            };
+            cursor.insert_before(elem);
         }
+        Ok(())
     }
-}
 
-impl<'a, T> ExactSizeIterator for Iter<'a, T> {
-    fn len(&self) -> usize {
-        self.len
+    pub fn remove(&mut self, at: usize) -> T {
+        let len = self.len;
+        match self.try_remove(at) {
+            Some(elem) => elem,
+            None => panic!("removal index (is {}) should be < len (is {})", at, len),
+        }
     }
-}
-
-impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
-    type IntoIter = IterMut<'a, T>;
-    type Item = &'a mut T;
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter_mut()
+    pub fn try_remove(&mut self, at: usize) -> Option<T> {
+        let cur = self.node_at(at);
+        let mut cursor = CursorMut {
+            cur,
+            list: self,
+            index: cur.map(|_| at),
+        };
+        // out of bounds leaves the cursor on the ghost, which removes nothing
+        cursor.remove_current()
     }
-}
 
-impl<'a, T> Iterator for IterMut<'a, T> {
-    type Item = &'a mut T;
+    pub fn swap(&mut self, a: usize, b: usize) {
+        if self.try_swap(a, b).is_none() {
+            panic!("index out of bounds: the len is {} but the index is {}", self.len, a.max(b));
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // While self.front == self.back is a tempting condition to check here,
-        // it won't do the right for yielding the last element! That sort of
-        // thing only works for arrays because of "one-past-the-end" pointers.
-        if self.len > 0 {
-            // We could unwrap front, but this is safer and easier
-            self.front.map(|node| unsafe {
-                self.len -= 1;
-                self.front = (*node.as_ptr()).back;
-                &mut (*node.as_ptr()).elem
-            })
-        } else {
-            None
+    pub fn try_swap(&mut self, a: usize, b: usize) -> Option<()> {
+        let node_a = self.node_at(a)?;
+        let node_b = self.node_at(b)?;
+        if a != b {
+            // distinct nodes, so these two borrows don't alias
+            unsafe {
+                std::mem::swap(&mut (*node_a.as_ptr()).elem, &mut (*node_b.as_ptr()).elem);
+            }
         }
+        Some(())
     }
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|elem| f(elem))
     }
-}
 
-impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        if self.len > 0 {
-            self.back.map(|node| unsafe {
-                self.len -= 1;
-                self.back = (*node.as_ptr()).front;
-                &mut (*node.as_ptr()).elem
-            })
-        } else {
-            None
-        }
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) {
+        self.extract_if(|elem| !f(elem)).for_each(drop);
     }
-}
 
-impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
-    fn len(&self) -> usize {
-        self.len
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b)
     }
-}
 
-impl<T> IntoIterator for LinkedList<T> {
-    type IntoIter = IntoIter<T>;
-    type Item = T;
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.into_iter()
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        unsafe {
+            // `kept` is always the last node we've decided to keep
+            let mut kept = match self.front {
+                Some(kept) => kept,
+                None => return,
+            };
+            while let Some(next) = (*kept.as_ptr()).back {
+                // same argument order as Vec: the candidate, then the keeper
+                if same_bucket(&mut (*next.as_ptr()).elem, &mut (*kept.as_ptr()).elem) {
+                    // unlink before dropping, so a panicking dtor can't
+                    // leave the list pointing at a freed node
+                    let after = (*next.as_ptr()).back;
+                    (*kept.as_ptr()).back = after;
+                    if let Some(after) = after {
+                        (*after.as_ptr()).front = Some(kept);
+                    } else {
+                        self.back = Some(kept);
+                    }
+                    self.len -= 1;
+                    self.forget_node(next);
+                    drop(Box::from_raw(next.as_ptr()));
+                } else {
+                    kept = next;
+                }
+            }
+        }
     }
-}
 
-impl<T> Iterator for IntoIter<T> {
-    type Item = T;
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T> {
+        let (start, end) = self.bounds(range);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.list.pop_front()
-    }
+        // Detach the whole range up front and stitch the ends back together,
+        // so whatever the Drain does (or doesn't) consume, the list is done.
+        let mut tail = self.detach_from(end);
+        let drained = self.detach_from(start);
+        self.link_back(&mut tail);
+        self.forget_nodes(&drained);
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.list.len, Some(self.list.len))
+        Drain {
+            iter: drained.into_iter(),
+            _boo: PhantomData,
+        }
     }
-}
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        self.list.pop_back()
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        ExtractIf { cursor, pred }
     }
-}
 
-impl<T> ExactSizeIterator for IntoIter<T> {
-    fn len(&self) -> usize {
-        self.list.len
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(|a, b| a.cmp(b))
     }
-}
 
-impl<T> Default for LinkedList<T> {
-    fn default() -> Self {
-        Self::new()
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
     }
-}
 
-impl<T: Clone> Clone for LinkedList<T> {
-    fn clone(&self) -> Self {
-        let mut new_list = Self::new();
-        for item in self {
-            new_list.push_back(item.clone());
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        if self.len < 2 {
+            return;
         }
-        new_list
-    }
-}
 
-impl<T> Extend<T> for LinkedList<T> {
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        for item in iter {
-            self.push_back(item);
+        // Take the nodes out of the list while we shuffle them around. The
+        // guard owns them until it's dropped, which links them back in: in
+        // order once we're done, or as they lie if `compare` panics, so
+        // nothing is ever lost or leaked.
+        self.len = 0;
+        self.back = None;
+        let front = self.front.take();
+        let mut guard = SortGuard {
+            list: self,
+            head: None,
+            tail: None,
+            p: None,
+            p_size: 0,
+            q: front,
+        };
+
+        // Bottom-up merge sort, treating the list as singly linked through
+        // `back` and fixing up the `front` links in one pass at the end.
+        unsafe {
+            let g = &mut guard;
+            let mut width = 1;
+            loop {
+                let mut merges = 0;
+
+                while g.q.is_some() {
+                    merges += 1;
+
+                    // p's run is the next `width` nodes, q starts after it
+                    g.p = g.q;
+                    while g.p_size < width {
+                        if let Some(node) = g.q {
+                            g.p_size += 1;
+                            g.q = (*node.as_ptr()).back;
+                        } else {
+                            break;
+                        }
+                    }
+                    let mut q_size = width;
+
+                    while g.p_size > 0 || (q_size > 0 && g.q.is_some()) {
+                        // taking from p on ties is what keeps this stable
+                        let take_p = if g.p_size == 0 {
+                            false
+                        } else if q_size == 0 || g.q.is_none() {
+                            true
+                        } else {
+                            let p_elem = &(*g.p.unwrap().as_ptr()).elem;
+                            let q_elem = &(*g.q.unwrap().as_ptr()).elem;
+                            compare(p_elem, q_elem) != Ordering::Greater
+                        };
+
+                        let node = if take_p {
+                            let node = g.p.unwrap();
+                            g.p = (*node.as_ptr()).back;
+                            g.p_size -= 1;
+                            node
+                        } else {
+                            let node = g.q.unwrap();
+                            g.q = (*node.as_ptr()).back;
+                            q_size -= 1;
+                            node
+                        };
+
+                        if let Some(tail) = g.tail {
+                            (*tail.as_ptr()).back = Some(node);
+                        } else {
+                            g.head = Some(node);
+                        }
+                        g.tail = Some(node);
+                    }
+                }
+
+                (*g.tail.unwrap().as_ptr()).back = None;
+                if merges <= 1 {
+                    break;
+                }
+                width *= 2;
+
+                // this pass's output is the next one's input
+                g.q = g.head.take();
+                g.tail = None;
+            }
         }
-    }
-}
+        // dropping the guard relinks the sorted chain and its `front` links
+    }
+
+    /// Links the chains a sort is working on back into the (emptied) list:
+    /// everything merged so far (`head` to `tail`), what's left of `p`'s run,
+    /// then `q` and everything after it. All three are chained through `back`.
+    unsafe fn relink_chains(
+        &mut self,
+        head: Link<T>,
+        tail: Link<T>,
+        mut p: Link<T>,
+        p_size: usize,
+        mut q: Link<T>,
+    ) {
+        let mut push = |node: NonNull<Node<T>>| {
+            (*node.as_ptr()).front = self.back;
+            (*node.as_ptr()).back = None;
+            if let Some(back) = self.back {
+                (*back.as_ptr()).back = Some(node);
+            } else {
+                self.front = Some(node);
+            }
+            self.back = Some(node);
+            self.len += 1;
+        };
 
-impl<T> FromIterator<T> for LinkedList<T> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut list = Self::new();
-        list.extend(iter);
-        list
+        let mut merged = head;
+        while let Some(node) = merged {
+            // the tail's `back` may still point into p or q
+            merged = if merged == tail { None } else { (*node.as_ptr()).back };
+            push(node);
+        }
+        for _ in 0..p_size {
+            let node = p.unwrap();
+            p = (*node.as_ptr()).back;
+            push(node);
+        }
+        while let Some(node) = q {
+            q = (*node.as_ptr()).back;
+            push(node);
+        }
     }
-}
 
-impl<T: Debug> Debug for LinkedList<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self).finish()
+    pub fn merge(&mut self, other: &mut Self)
+    where
+        T: Ord,
+    {
+        self.merge_by(other, |a, b| a.cmp(b))
     }
-}
 
-impl<T: PartialEq> PartialEq for LinkedList<T> {
-    fn eq(&self, other: &Self) -> bool {
-        self.len() == other.len() && self.iter().eq(other)
+    pub fn merge_by<F: FnMut(&T, &T) -> Ordering>(&mut self, other: &mut Self, mut compare: F) {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        while !other.is_empty() {
+            let cur = match cursor.current() {
+                Some(cur) => &*cur,
+                None => {
+                    // ran off our back, everything left in other goes after us
+                    cursor.splice_before(std::mem::take(other));
+                    break;
+                }
+            };
+
+            // Move the whole run of other's elements that sort before `cur`
+            // in one splice. Ties stay behind `cur`, which keeps it stable.
+            let run = other.iter()
+                .take_while(|elem| compare(cur, elem) == Ordering::Greater)
+                .count();
+            if run > 0 {
+                let rest = other.split_off(run);
+                cursor.splice_before(std::mem::replace(other, rest));
+            }
+            cursor.move_next();
+        }
     }
-}
 
-impl<T: Eq> Eq for LinkedList<T> {  }
+    pub fn insert_sorted(&mut self, elem: T)
+    where
+        T: Ord,
+    {
+        self.insert_sorted_by(elem, |a, b| a.cmp(b))
+    }
 
-impl<T: PartialOrd> PartialOrd for LinkedList<T> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.iter().partial_cmp(other)
+    pub fn insert_sorted_by<F: FnMut(&T, &T) -> Ordering>(&mut self, elem: T, mut compare: F) {
+        // goes in front of the first element that isn't less than it,
+        // or at the back (in front of the ghost) if there isn't one
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        while let Some(cur) = cursor.current() {
+            if compare(cur, &elem) != Ordering::Less {
+                break;
+            }
+            cursor.move_next();
+        }
+        cursor.insert_before(elem);
     }
-}
 
-impl<T: Ord> Ord for LinkedList<T> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.iter().cmp(other)
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.iter().is_sorted()
     }
-}
 
-impl<T: Hash> Hash for LinkedList<T> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.len().hash(state);
-        for item in self {
-            item.hash(state);
+    pub fn front(&self) -> Option<&T> {
+        unsafe {
+            Some(&(*self.front?.as_ptr()).elem)
         }
     }
-}
 
-impl<'a, T> CursorMut<'a, T> {
-    pub fn index(&self) -> Option<usize> {
-        self.index
+    pub fn back(&self) -> Option<&T> {
+        unsafe {
+            Some(&(*self.back?.as_ptr()).elem)
+        }
     }
 
-    pub fn move_next(&mut self) {
-        if let Some(cur) = self.cur {
-            unsafe {
-                // We're on a real element, go to its next (back)
-                self.cur = (*cur.as_ptr()).back;
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.front.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
 
-                if self.cur.is_some() {
-                    *self.index.as_mut().unwrap() += 1;
-                } else {
-                    // we just walked to the ghost, no more index
-                    self.index = None
-                }
-            }
-        } else if !self.list.is_empty() {
-            // we're at the ghost, and there is a real front, so move to it
-            self.cur = self.list.front;
-            self.index = Some(0);
-        } else {
-            // We're at the ghost, but it's the only element... do nothing
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.back.map(|node| &mut (*node.as_ptr()).elem)
         }
     }
 
-    pub fn move_prev(&mut self) {
-        if let Some(cur) = self.cur {
-            unsafe {
-                self.cur = (*cur.as_ptr()).front;
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
 
-                if self.cur.is_some() {
-                    *self.index.as_mut().unwrap() -= 1;
-                } else {
-                    self.index = None;
-                }
-            }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
 
-        } else if !self.list.is_empty() {
-            self.cur = self.list.back;
-            self.index = Some(self.list.len - 1);
+    pub fn pairs(&self) -> Pairs<'_, T> {
+        Pairs {
+            front: self.front,
+            back: self.back,
+            len: self.len.saturating_sub(1),
+            _boo: PhantomData,
         }
     }
 
-    pub fn current(&mut self) -> Option<&mut T> {
-        unsafe {
-            self.cur.map(|node| &mut (*node.as_ptr()).elem)
+    pub fn pairs_mut(&mut self) -> PairsMut<'_, T> {
+        PairsMut {
+            front: self.front,
+            back: self.back,
+            len: self.len.saturating_sub(1),
+            _boo: PhantomData,
         }
     }
 
-    pub fn peek_next(&mut self) -> Option<&mut T> {
-        unsafe {
-            let next = if let Some(cur) = self.cur {
-                (*cur.as_ptr()).back
-            } else {
-                self.list.front
-            };
-            next.map(|node| &mut (*node.as_ptr()).elem)
+    pub fn iter_range<R: RangeBounds<usize>>(&self, range: R) -> Iter<'_, T> {
+        let (start, end) = self.bounds(range);
+        Iter {
+            front: self.node_at(start),
+            back: end.checked_sub(1).and_then(|last| self.node_at(last)),
+            len: end - start,
+            _boo: PhantomData,
         }
     }
 
-    pub fn peek_prev(&mut self) -> Option<&mut T> {
-        unsafe {
-            let prev = if let Some(cur) = self.cur {
-                (*cur.as_ptr()).front
-            } else {
-                self.list.back
-            };
-            prev.map(|node| &mut (*node.as_ptr()).elem)
+    pub fn iter_range_mut<R: RangeBounds<usize>>(&mut self, range: R) -> IterMut<'_, T> {
+        let (start, end) = self.bounds(range);
+        IterMut {
+            front: self.node_at(start),
+            back: end.checked_sub(1).and_then(|last| self.node_at(last)),
+            len: end - start,
+            _boo: PhantomData,
         }
     }
 
-    pub fn split_before(&mut self) -> LinkedList<T> {
-        if let Some(cur) = self.cur {
-            unsafe {
-                // current state
-                let old_len = self.list.len;
-                let old_idx = self.index.unwrap();
-                let prev = (*cur.as_ptr()).front;
+    pub fn iter_mut_split(&mut self, at: usize) -> (IterMut<'_, T>, IterMut<'_, T>) {
+        assert!(at <= self.len, "mid > len");
+        // The halves never hand out the same node twice, since each one
+        // stops once its own `len` runs out, so the &mut T's can't alias.
+        let mid = self.node_at(at);
+        let first_back = match mid {
+            Some(mid) => unsafe { (*mid.as_ptr()).front },
+            None => self.back,
+        };
+        let first = IterMut {
+            front: self.front,
+            back: first_back,
+            len: at,
+            _boo: PhantomData,
+        };
+        let second = IterMut {
+            front: mid,
+            back: self.back,
+            len: self.len - at,
+            _boo: PhantomData,
+        };
+        (first, second)
+    }
 
-                // what self will become
-                let new_len = old_len - old_idx;
-                let new_front = self.cur;
-                let new_back = self.list.back;
-                let new_idx = Some(0);
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            list: self,
+            cur: None,
+            index: None,
+        }
+    }
 
-                // what the output will become
-                let output_len = old_len - new_len;
-                let output_front = self.list.front;
-                let output_back = prev;
+    pub fn cursor_mut_at(&mut self, index: usize) -> CursorMut<'_, T> {
+        let mut cursor = self.cursor_mut();
+        cursor.seek(index);
+        cursor
+    }
 
-                if let Some(prev) = prev {
-                    (*cur.as_ptr()).front = None;
-                    (*prev.as_ptr()).back = None;
-                }
+    pub fn cursor_mut_find<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> CursorMut<'_, T> {
+        let mut cursor = self.cursor_mut();
+        cursor.move_next();
+        // stop on the first match, or on the ghost if we walk off the back
+        while let Some(elem) = cursor.current() {
+            if pred(elem) {
+                break;
+            }
+            cursor.move_next();
+        }
+        cursor
+    }
 
-                self.list.len = new_len;
-                self.list.front = new_front;
-                self.list.back = new_back;
-                self.index = new_idx;
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            cur: self.front,
+            // an empty list puts us on the ghost
+            index: self.front.map(|_| 0),
+            list: self,
+        }
+    }
 
-                LinkedList {
-                    front: output_front,
-                    back: output_back,
-                    len: output_len,
-                    _boo: PhantomData,
-                }
-            }
-        } else {
-            // we're at the ghost, just replace our list with an empty one.
-            // no other state needs to be changed
-            std::mem::replace(self.list, LinkedList::new())
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            cur: self.back,
+            index: self.back.map(|_| self.len - 1),
+            list: self,
         }
     }
 
-    pub fn split_after(&mut self) -> LinkedList<T> {
-        if let Some(cur) = self.cur {
-            unsafe {
-                // current state
-                let old_len = self.list.len;
-                let old_idx = self.index.unwrap();
-                let next = (*cur.as_ptr()).back;
+    pub fn push_front_handle(&mut self, elem: T) -> NodeHandle<T> {
+        self.push_front(elem);
+        self.make_handle(self.front.unwrap())
+    }
 
-                // what self will become
-                let new_len = old_len - old_idx;
-                let new_front = self.list.front;
-                let new_back = self.cur;
-                let new_idx = Some(0);
+    pub fn push_back_handle(&mut self, elem: T) -> NodeHandle<T> {
+        self.push_back(elem);
+        self.make_handle(self.back.unwrap())
+    }
 
-                // what the output will become
-                let output_len = old_len - new_len;
-                let output_back = self.list.back;
-                let output_front = next;
+    pub fn get_by_handle(&self, handle: &NodeHandle<T>) -> Option<&T> {
+        unsafe {
+            self.check_handle(handle).map(|node| &(*node.as_ptr()).elem)
+        }
+    }
 
-                if let Some(next) = next {
-                    (*cur.as_ptr()).front = None;
-                    (*next.as_ptr()).back = None;
-                }
+    pub fn get_mut_by_handle(&mut self, handle: &NodeHandle<T>) -> Option<&mut T> {
+        unsafe {
+            self.check_handle(handle).map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
 
-                self.list.len = new_len;
-                self.list.front = new_front;
-                self.list.back = new_back;
-                self.index = new_idx;
+    pub fn remove_by_handle(&mut self, handle: &NodeHandle<T>) -> Option<T> {
+        self.cursor_at(handle)?.remove_current()
+    }
 
-                LinkedList {
-                    front: output_front,
-                    back: output_back,
-                    len: output_len,
-                    _boo: PhantomData,
-                }
-            }
+    pub fn cursor_at(&mut self, handle: &NodeHandle<T>) -> Option<CursorMut<'_, T>> {
+        let cur = self.check_handle(handle)?;
+        Some(CursorMut {
+            cur: Some(cur),
+            list: self,
+            // worked out when somebody asks, see LinkedList::cursor_index
+            index: None,
+        })
+    }
+
+    fn make_handle(&mut self, node: NonNull<Node<T>>) -> NodeHandle<T> {
+        if self.handles.len() > 2 * self.len {
+            // Only entries from before a bulk kill can be for nodes we don't
+            // have, so at least half of these go. Each entry is swept once,
+            // which pays for this out of the inserts that made them.
+            let from = self.handles_from;
+            self.handles.retain(|_, generation| *generation >= from);
+        }
+        let generation = next_generation();
+        self.handles.insert(node, generation);
+        NodeHandle { node, generation }
+    }
+
+    fn check_handle(&self, handle: &NodeHandle<T>) -> Link<T> {
+        // Entries from `handles_from` on are only ever for nodes in this
+        // list, which we're borrowing. Generations never repeat, so a handle
+        // to a freed node can't match whatever got allocated there later.
+        if handle.generation >= self.handles_from
+            && self.handles.get(&handle.node) == Some(&handle.generation)
+        {
+            Some(handle.node)
         } else {
-            // we're at the ghost, just replace our list with an empty one.
-            // no other state needs to be changed
-            std::mem::replace(self.list, LinkedList::new())
+            None
         }
     }
 
-    pub fn splice_before(&mut self, mut input: LinkedList<T>) {
-        unsafe {
-            // we can either `take` the input's ptrs or `mem::forget`
-            // it. using take is more responsible in case we do custom
-            // allocators or something that also needs to be cleaned up
-            if input.is_empty() {
-                // do nothing
-            } else if let Some(cur) = self.cur {
-                // both lists are non-empty
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
+    /// Kills every handle into us without touching the map.
+    fn forget_all(&mut self) {
+        if !self.handles.is_empty() {
+            self.handles_from = next_generation();
+        }
+    }
 
-                if let Some(prev) = (*cur.as_ptr()).front {
-                    // general case, no boundaries, just internal fixups
-                    (*prev.as_ptr()).back = Some(in_front);
-                    (*in_front.as_ptr()).front = Some(prev);
-                    (*cur.as_ptr()).front = Some(in_back);
-                    (*in_back.as_ptr()).back = Some(cur);
+    /// Kills the handle to `node`, which is leaving us.
+    fn forget_node(&mut self, node: NonNull<Node<T>>) {
+        if !self.handles.is_empty() {
+            self.handles.remove(&node);
+        }
+    }
 
-                } else {
-                    // we're appending to the front, see append to back
-                    (*cur.as_ptr()).front = Some(in_back);
-                    (*in_back.as_ptr()).back = Some(cur);
-                    self.list.front = Some(in_front);
-                }
-                // index moves forward by input length
-                *self.index.as_mut().unwrap() += input.len;
-            } else if let Some(back) = self.list.back {
-                // we're on the ghost, but non-empty, append to the back
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
+    /// Kills the handles to everything in `gone`, which just left us.
+    fn forget_nodes(&mut self, gone: &Self) {
+        if self.handles.is_empty() {
+            return;
+        }
+        let mut cur = gone.front;
+        while let Some(node) = cur {
+            self.handles.remove(&node);
+            unsafe {
+                cur = (*node.as_ptr()).back;
+            }
+        }
+    }
 
-                (*back.as_ptr()).back = Some(in_front);
-                (*in_front.as_ptr()).front = Some(back);
-                self.list.back = Some(in_back);
+    /// Ties all of `other`'s nodes onto our back. Handles are left to the
+    /// caller.
+    fn link_back(&mut self, other: &mut Self) {
+        if let Some(other_front) = other.front.take() {
+            if let Some(back) = self.back {
+                unsafe {
+                    (*back.as_ptr()).back = Some(other_front);
+                    (*other_front.as_ptr()).front = Some(back);
+                }
             } else {
-                // we're empty, become the input, remain on the ghost
-                std::mem::swap(self.list, &mut input);
+                self.front = Some(other_front);
             }
-            self.list.len += input.len;
-            input.len = 0;
+            self.back = other.back.take();
+            self.len += std::mem::replace(&mut other.len, 0);
         }
     }
 
-    pub fn splice_after(&mut self, mut input: LinkedList<T>) {
-        unsafe {
-            // we can either `take` the input's ptrs or `mem::forget`
-            // it. using take is more responsible in case we do custom
-            // allocators or something that also needs to be cleaned up
-            if input.is_empty() {
-                // do nothing
-            } else if let Some(cur) = self.cur {
-                // both lists are non-empty
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
-
-                if let Some(next) = (*cur.as_ptr()).back {
-                    // general case, no boundaries, just internal fixups
-                    (*next.as_ptr()).front = Some(in_back);
-                    (*in_back.as_ptr()).back = Some(next);
-                    (*cur.as_ptr()).back = Some(in_front);
-                    (*in_front.as_ptr()).front = Some(cur);
+    /// Moves all our nodes out, but keeps our handle map.
+    fn take_nodes(&mut self) -> Self {
+        LinkedList {
+            front: self.front.take(),
+            back: self.back.take(),
+            len: std::mem::replace(&mut self.len, 0),
+            handles: HandleMap::default(),
+            handles_from: 0,
+            _boo: PhantomData,
+        }
+    }
 
-                } else {
-                    // we're appending to the front, see append to back
-                    (*cur.as_ptr()).back = Some(in_front);
-                    (*in_front.as_ptr()).front = Some(cur);
-                    self.list.back = Some(in_back);
+    /// Cuts off everything from `at` on. Handles are left to the caller.
+    fn detach_from(&mut self, at: usize) -> Self {
+        if at == 0 {
+            return self.take_nodes();
+        }
+        // park a cursor on the last element we keep and let it do the surgery
+        let cur = self.node_at(at - 1);
+        let mut cursor = CursorMut {
+            cur,
+            list: self,
+            index: Some(at - 1),
+        };
+        cursor.detach_after()
+    }
+
+    /// Cuts off everything before `at`. Handles are left to the caller.
+    fn detach_to(&mut self, at: usize) -> Self {
+        if at == self.len {
+            return self.take_nodes();
+        }
+        let cur = self.node_at(at);
+        let mut cursor = CursorMut {
+            cur,
+            list: self,
+            index: Some(at),
+        };
+        cursor.detach_before()
+    }
+
+    /// The index of a cursor at `cur` that knows it's at `index`. A cursor
+    /// made from a NodeHandle doesn't know yet (`cur` is set, `index` is
+    /// None), so we work it out here rather than walking when it's made.
+    fn cursor_index(&self, cur: Link<T>, index: Option<usize>) -> Option<usize> {
+        index.or_else(|| Some(self.index_of(cur?)))
+    }
+
+    /// Works out where `node` sits, walking out from it in both directions
+    /// until one side runs into an end.
+    fn index_of(&self, node: NonNull<Node<T>>) -> usize {
+        unsafe {
+            let mut to_front = node;
+            let mut to_back = node;
+            let mut steps = 0;
+            loop {
+                match (*to_front.as_ptr()).front {
+                    Some(prev) => to_front = prev,
+                    None => return steps,
                 }
-            } else if let Some(front) = self.list.front {
-                // we're on the ghost, but non-empty, append to the back
-                let in_front = input.front.take().unwrap();
-                let in_back = input.back.take().unwrap();
+                match (*to_back.as_ptr()).back {
+                    Some(next) => to_back = next,
+                    None => return self.len - 1 - steps,
+                }
+                steps += 1;
+            }
+        }
+    }
 
-                (*front.as_ptr()).front = Some(in_back);
-                (*in_back.as_ptr()).back = Some(front);
-                self.list.front = Some(in_front);
-            } else {
-                // we're empty, become the input, remain on the ghost
-                std::mem::swap(self.list, &mut input);
+    /// Resolves `range` against our length, panicking like slicing would.
+    fn bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "slice index starts at {} but ends at {}", start, end);
+        assert!(end <= self.len, "range end index {} out of range for slice of length {}", end, self.len);
+        (start, end)
+    }
+
+    /// Finds the node at `at`, walking in from whichever end is closer.
+    fn node_at(&self, at: usize) -> Link<T> {
+        if at >= self.len {
+            return None;
+        }
+        unsafe { nth_node(self.front, self.back, self.len, at) }
+    }
+}
+
+/// Finds the node `n` steps in from `front`, where `front..=back` is a run
+/// of `len` nodes, walking in from whichever end is closer. The iterators
+/// use this on whatever is left of their range.
+unsafe fn nth_node<T>(front: Link<T>, back: Link<T>, len: usize, n: usize) -> Link<T> {
+    if n < len / 2 {
+        let mut cur = front;
+        for _ in 0..n {
+            cur = (*cur?.as_ptr()).back;
+        }
+        cur
+    } else {
+        let mut cur = back;
+        for _ in n + 1..len {
+            cur = (*cur?.as_ptr()).front;
+        }
+        cur
+    }
+}
+
+/// Owns the nodes of a list `sort_by` is in the middle of. See `relink_chains`
+/// for what each field holds.
+struct SortGuard<'a, T> {
+    list: &'a mut LinkedList<T>,
+    head: Link<T>,
+    tail: Link<T>,
+    p: Link<T>,
+    p_size: usize,
+    q: Link<T>,
+}
+
+impl<T> Drop for SortGuard<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.list.relink_chains(self.head, self.tail, self.p, self.p_size, self.q);
+        }
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        // Pop until we have to stop
+        while self.pop_front().is_some() { }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // While self.front == self.back is a tempting condition to check here,
+        // it won't do the right for yielding the last element! That sort of
+        // thing only works for arrays because of "one-past-the-end" pointers.
+        if self.len > 0 {
+            // We could unwrap front, but this is safer and easier
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &(*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn count(self) -> usize {
+        self.len
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe { &(*node.as_ptr()).elem })
+        } else {
+            None
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        unsafe {
+            let node = nth_node(self.front, self.back, self.len, n)?;
+            self.len -= n + 1;
+            self.front = (*node.as_ptr()).back;
+            Some(&(*node.as_ptr()).elem)
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        let mut cur = self.front;
+        for _ in 0..self.len {
+            unsafe {
+                let node = cur.unwrap();
+                cur = (*node.as_ptr()).back;
+                acc = f(acc, &(*node.as_ptr()).elem);
             }
-            self.list.len += input.len;
-            input.len = 0;
         }
+        acc
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &(*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        unsafe {
+            let node = nth_node(self.front, self.back, self.len, self.len - 1 - n)?;
+            self.len -= n + 1;
+            self.back = (*node.as_ptr()).front;
+            Some(&(*node.as_ptr()).elem)
+        }
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        let mut cur = self.back;
+        for _ in 0..self.len {
+            unsafe {
+                let node = cur.unwrap();
+                cur = (*node.as_ptr()).front;
+                acc = f(acc, &(*node.as_ptr()).elem);
+            }
+        }
+        acc
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+// Derive would ask for T: Clone, but we only copy pointers
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+}
+
+/// Shows whatever an iterator has left, as a list.
+struct Remaining<'a, T>(Iter<'a, T>);
+
+impl<'a, T: Debug> Debug for Remaining<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.clone()).finish()
+    }
+}
+
+impl<'a, T: Debug> Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&Remaining(self.clone())).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = &'a mut T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // While self.front == self.back is a tempting condition to check here,
+        // it won't do the right for yielding the last element! That sort of
+        // thing only works for arrays because of "one-past-the-end" pointers.
+        if self.len > 0 {
+            // We could unwrap front, but this is safer and easier
+            self.front.map(|node| unsafe {
+                self.len -= 1;
+                self.front = (*node.as_ptr()).back;
+                &mut (*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    fn count(self) -> usize {
+        self.len
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe { &mut (*node.as_ptr()).elem })
+        } else {
+            None
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        unsafe {
+            let node = nth_node(self.front, self.back, self.len, n)?;
+            self.len -= n + 1;
+            self.front = (*node.as_ptr()).back;
+            Some(&mut (*node.as_ptr()).elem)
+        }
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        let mut cur = self.front;
+        for _ in 0..self.len {
+            unsafe {
+                let node = cur.unwrap();
+                cur = (*node.as_ptr()).back;
+                acc = f(acc, &mut (*node.as_ptr()).elem);
+            }
+        }
+        acc
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                self.len -= 1;
+                self.back = (*node.as_ptr()).front;
+                &mut (*node.as_ptr()).elem
+            })
+        } else {
+            None
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        unsafe {
+            let node = nth_node(self.front, self.back, self.len, self.len - 1 - n)?;
+            self.len -= n + 1;
+            self.back = (*node.as_ptr()).front;
+            Some(&mut (*node.as_ptr()).elem)
+        }
+    }
+
+    fn rfold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        let mut cur = self.back;
+        for _ in 0..self.len {
+            unsafe {
+                let node = cur.unwrap();
+                cur = (*node.as_ptr()).front;
+                acc = f(acc, &mut (*node.as_ptr()).elem);
+            }
+        }
+        acc
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
+
+impl<'a, T: Debug> Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The &mut T's we've already handed out are all outside of
+        // front..=back, so a shared look at what's left doesn't alias them
+        let remaining = Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        };
+        f.debug_tuple("IterMut").field(&Remaining(remaining)).finish()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type IntoIter = IntoIter<T>;
+    type Item = T;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            list: self
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+
+    fn count(self) -> usize {
+        self.list.len
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        // drop everything we skip over in one go, then take the next one
+        self.list.truncate_front(self.list.len.saturating_sub(n));
+        self.list.pop_front()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.list.truncate(self.list.len.saturating_sub(n));
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {
+    fn len(&self) -> usize {
+        self.list.len
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T: Debug> Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.list).finish()
+    }
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Drain<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Drain<'a, T> {
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a, T, F> Iterator for ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Every match is unlinked before we yield it, so the list is always
+        // consistent and dropping us early just leaves the rest in place.
+        // Note we only step while on a real element: stepping off the ghost
+        // would wrap back around to the front.
+        while let Some(elem) = self.cursor.current() {
+            if (self.pred)(elem) {
+                return self.cursor.remove_current();
+            }
+            self.cursor.move_next();
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match self.cursor.index() {
+            Some(idx) => self.cursor.list.len - idx,
+            None => 0,
+        };
+        (0, Some(remaining))
+    }
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.front.map(|node| unsafe {
+                // there's a pair left, so there's a node after this one
+                let next = (*node.as_ptr()).back.unwrap();
+                self.len -= 1;
+                self.front = Some(next);
+                (&(*node.as_ptr()).elem, &(*next.as_ptr()).elem)
+            })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Pairs<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                let prev = (*node.as_ptr()).front.unwrap();
+                self.len -= 1;
+                self.back = Some(prev);
+                (&(*prev.as_ptr()).elem, &(*node.as_ptr()).elem)
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Pairs<'a, T> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T> FusedIterator for Pairs<'a, T> {}
+
+impl<'a, T> PairsMut<'a, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The pairs are only borrowed from `self`, not 'a, which is what stops
+    // two overlapping pairs from being alive at the same time.
+    pub fn next_pair(&mut self) -> Option<(&mut T, &mut T)> {
+        if self.len > 0 {
+            self.front.map(|node| unsafe {
+                let next = (*node.as_ptr()).back.unwrap();
+                self.len -= 1;
+                self.front = Some(next);
+                (&mut (*node.as_ptr()).elem, &mut (*next.as_ptr()).elem)
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn next_pair_back(&mut self) -> Option<(&mut T, &mut T)> {
+        if self.len > 0 {
+            self.back.map(|node| unsafe {
+                let prev = (*node.as_ptr()).front.unwrap();
+                self.len -= 1;
+                self.back = Some(prev);
+                (&mut (*prev.as_ptr()).elem, &mut (*node.as_ptr()).elem)
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        let mut new_list = Self::new();
+        for item in self {
+            new_list.push_back(item.clone());
+        }
+        new_list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push_back(item);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
+    fn from(arr: [T; N]) -> Self {
+        arr.into_iter().collect()
+    }
+}
+
+impl<T> From<VecDeque<T>> for LinkedList<T> {
+    fn from(deque: VecDeque<T>) -> Self {
+        deque.into_iter().collect()
+    }
+}
+
+impl<T> From<collections::LinkedList<T>> for LinkedList<T> {
+    fn from(list: collections::LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T> From<LinkedList<T>> for Vec<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T> From<LinkedList<T>> for VecDeque<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T> From<LinkedList<T>> for collections::LinkedList<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+impl<T> Index<usize> for LinkedList<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(elem) => elem,
+            None => panic!("index out of bounds: the len is {} but the index is {}", self.len, index),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for LinkedList<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.len;
+        match self.get_mut(index) {
+            Some(elem) => elem,
+            None => panic!("index out of bounds: the len is {} but the index is {}", len, index),
+        }
+    }
+}
+
+impl<T: Debug> Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {  }
+
+impl<T: PartialEq> PartialEq<[T]> for LinkedList<T> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T: PartialEq> PartialEq<Vec<T>> for LinkedList<T> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for LinkedList<T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self {
+            item.hash(state);
+        }
+    }
+}
+
+impl<'a, T> Cursor<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.list.cursor_index(self.cur, self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // We're on a real element, go to its next (back)
+                self.cur = (*cur.as_ptr()).back;
+
+                if self.cur.is_some() {
+                    self.index = self.index.map(|index| index + 1);
+                } else {
+                    // we just walked to the ghost, no more index
+                    self.index = None
+                }
+            }
+        } else if !self.list.is_empty() {
+            // we're at the ghost, and there is a real front, so move to it
+            self.cur = self.list.front;
+            self.index = Some(0);
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                self.cur = (*cur.as_ptr()).front;
+
+                if self.cur.is_some() {
+                    self.index = self.index.map(|index| index - 1);
+                } else {
+                    self.index = None;
+                }
+            }
+        } else if !self.list.is_empty() {
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    // Unlike CursorMut, these can hand out the full 'a lifetime, since
+    // nobody can mutate the list while we're borrowing it
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe {
+            self.cur.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        unsafe {
+            let next = if let Some(cur) = self.cur {
+                (*cur.as_ptr()).back
+            } else {
+                self.list.front
+            };
+            next.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        unsafe {
+            let prev = if let Some(cur) = self.cur {
+                (*cur.as_ptr()).front
+            } else {
+                self.list.back
+            };
+            prev.map(|node| &(*node.as_ptr()).elem)
+        }
+    }
+}
+
+impl<T> Clone for NodeHandle<T> {
+    fn clone(&self) -> Self {
+        NodeHandle {
+            node: self.node,
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> Debug for NodeHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeHandle")
+            .field("node", &self.node)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+// Derive would ask for T: Clone, but we only copy pointers
+impl<'a, T> Clone for Cursor<'a, T> {
+    fn clone(&self) -> Self {
+        Cursor {
+            cur: self.cur,
+            list: self.list,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.list.cursor_index(self.cur, self.index)
+    }
+
+    pub fn seek(&mut self, index: usize) {
+        let len = self.list.len;
+        if index >= len {
+            // nothing lives there, park on the ghost
+            self.cur = None;
+            self.index = None;
+            return;
+        }
+
+        // start from the front, the back or where we are, whichever's closest
+        let from_back = len - 1 - index;
+        let (mut cur, mut at) = if from_back < index {
+            (self.list.back, len - 1)
+        } else {
+            (self.list.front, 0)
+        };
+        // a cursor that doesn't know its index yet can't be a starting point
+        if let (Some(here), Some(here_idx)) = (self.cur, self.index) {
+            if here_idx.abs_diff(index) < index.min(from_back) {
+                cur = Some(here);
+                at = here_idx;
+            }
+        }
+
+        unsafe {
+            while at < index {
+                cur = (*cur.unwrap().as_ptr()).back;
+                at += 1;
+            }
+            while at > index {
+                cur = (*cur.unwrap().as_ptr()).front;
+                at -= 1;
+            }
+        }
+        self.cur = cur;
+        self.index = Some(index);
+    }
+
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            cur: self.cur,
+            list: self.list,
+            index: self.index,
+        }
+    }
+
+    pub fn move_next(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // We're on a real element, go to its next (back)
+                self.cur = (*cur.as_ptr()).back;
+
+                if self.cur.is_some() {
+                    self.index = self.index.map(|index| index + 1);
+                } else {
+                    // we just walked to the ghost, no more index
+                    self.index = None
+                }
+            }
+        } else if !self.list.is_empty() {
+            // we're at the ghost, and there is a real front, so move to it
+            self.cur = self.list.front;
+            self.index = Some(0);
+        } else {
+            // We're at the ghost, but it's the only element... do nothing
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        if let Some(cur) = self.cur {
+            unsafe {
+                self.cur = (*cur.as_ptr()).front;
+
+                if self.cur.is_some() {
+                    self.index = self.index.map(|index| index - 1);
+                } else {
+                    self.index = None;
+                }
+            }
+
+        } else if !self.list.is_empty() {
+            self.cur = self.list.back;
+            self.index = Some(self.list.len - 1);
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe {
+            self.cur.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe {
+            let next = if let Some(cur) = self.cur {
+                (*cur.as_ptr()).back
+            } else {
+                self.list.front
+            };
+            next.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        unsafe {
+            let prev = if let Some(cur) = self.cur {
+                (*cur.as_ptr()).front
+            } else {
+                self.list.back
+            };
+            prev.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    pub fn split_before(&mut self) -> LinkedList<T> {
+        let output = self.detach_before();
+        if !output.is_empty() {
+            // finding the handles that left would mean walking the output
+            self.list.forget_all();
+        }
+        output
+    }
+
+    pub fn split_after(&mut self) -> LinkedList<T> {
+        let output = self.detach_after();
+        if !output.is_empty() {
+            self.list.forget_all();
+        }
+        output
+    }
+
+    /// `split_before`, leaving the handles to the caller.
+    fn detach_before(&mut self) -> LinkedList<T> {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // current state
+                let old_len = self.list.len;
+                let old_idx = self.index().unwrap();
+                let prev = (*cur.as_ptr()).front;
+
+                // what self will become
+                let new_len = old_len - old_idx;
+                let new_front = self.cur;
+                let new_back = self.list.back;
+                let new_idx = Some(0);
+
+                // what the output will become
+                let output_len = old_len - new_len;
+                let output_front = if prev.is_some() { self.list.front } else { None };
+                let output_back = prev;
+
+                if let Some(prev) = prev {
+                    (*cur.as_ptr()).front = None;
+                    (*prev.as_ptr()).back = None;
+                }
+
+                self.list.len = new_len;
+                self.list.front = new_front;
+                self.list.back = new_back;
+                self.index = new_idx;
+
+                LinkedList {
+                    front: output_front,
+                    back: output_back,
+                    len: output_len,
+                    handles: HandleMap::default(),
+                    handles_from: 0,
+                    _boo: PhantomData,
+                }
+            }
+        } else {
+            // we're at the ghost, just take all the list's nodes.
+            // no other state needs to be changed
+            self.list.take_nodes()
+        }
+    }
+
+    /// `split_after`, leaving the handles to the caller.
+    fn detach_after(&mut self) -> LinkedList<T> {
+        if let Some(cur) = self.cur {
+            unsafe {
+                // current state
+                let old_len = self.list.len;
+                let old_idx = self.index().unwrap();
+                let next = (*cur.as_ptr()).back;
+
+                // what self will become
+                let new_len = old_idx + 1;
+                let new_front = self.list.front;
+                let new_back = self.cur;
+                let new_idx = Some(old_idx);
+
+                // what the output will become
+                let output_len = old_len - new_len;
+                let output_front = next;
+                let output_back = if next.is_some() { self.list.back } else { None };
+
+                if let Some(next) = next {
+                    (*cur.as_ptr()).back = None;
+                    (*next.as_ptr()).front = None;
+                }
+
+                self.list.len = new_len;
+                self.list.front = new_front;
+                self.list.back = new_back;
+                self.index = new_idx;
+
+                LinkedList {
+                    front: output_front,
+                    back: output_back,
+                    len: output_len,
+                    handles: HandleMap::default(),
+                    handles_from: 0,
+                    _boo: PhantomData,
+                }
+            }
+        } else {
+            // we're at the ghost, just take all the list's nodes.
+            // no other state needs to be changed
+            self.list.take_nodes()
+        }
+    }
+
+    pub fn insert_before(&mut self, elem: T) {
+        // a one element list is just the node we'd allocate anyway
+        let mut input = LinkedList::new();
+        input.push_back(elem);
+        self.splice_before(input);
+    }
+
+    pub fn insert_after(&mut self, elem: T) {
+        let mut input = LinkedList::new();
+        input.push_back(elem);
+        self.splice_after(input);
+    }
+
+    pub fn remove_current(&mut self) -> Option<T> {
+        self.remove_current_as_list()?.pop_front()
+    }
+
+    pub fn remove_current_as_list(&mut self) -> Option<LinkedList<T>> {
+        // nothing to remove at the ghost
+        let cur = self.cur?;
+        unsafe {
+            let prev = (*cur.as_ptr()).front;
+            let next = (*cur.as_ptr()).back;
+
+            // stitch our neighbours (or the list's ends) together
+            if let Some(prev) = prev {
+                (*prev.as_ptr()).back = next;
+            } else {
+                self.list.front = next;
+            }
+            if let Some(next) = next {
+                (*next.as_ptr()).front = prev;
+            } else {
+                self.list.back = prev;
+            }
+
+            (*cur.as_ptr()).front = None;
+            (*cur.as_ptr()).back = None;
+            self.list.len -= 1;
+
+            // the next element slides into our index, unless it's the ghost
+            self.cur = next;
+            if next.is_none() {
+                self.index = None;
+            }
+
+            self.list.forget_node(cur);
+            Some(LinkedList {
+                front: Some(cur),
+                back: Some(cur),
+                len: 1,
+                handles: HandleMap::default(),
+                handles_from: 0,
+                _boo: PhantomData,
+            })
+        }
+    }
+
+    pub fn remove_range(&mut self, n: usize) -> LinkedList<T> {
+        if n == 0 {
+            return LinkedList::new();
+        }
+        let first = self.cur.expect("cannot remove a range starting at the ghost");
+        unsafe {
+            // find the other end of the range, this is the only O(n) part
+            let mut last = first;
+            for _ in 1..n {
+                last = (*last.as_ptr()).back.expect("range extends past the back of the list");
+            }
+
+            let prev = (*first.as_ptr()).front;
+            let next = (*last.as_ptr()).back;
+
+            if let Some(prev) = prev {
+                (*prev.as_ptr()).back = next;
+            } else {
+                self.list.front = next;
+            }
+            if let Some(next) = next {
+                (*next.as_ptr()).front = prev;
+            } else {
+                self.list.back = prev;
+            }
+            (*first.as_ptr()).front = None;
+            (*last.as_ptr()).back = None;
+            self.list.len -= n;
+
+            // like remove_current, we end up on whatever followed the range
+            self.cur = next;
+            if next.is_none() {
+                self.index = None;
+            }
+
+            let range = LinkedList {
+                front: Some(first),
+                back: Some(last),
+                len: n,
+                handles: HandleMap::default(),
+                handles_from: 0,
+                _boo: PhantomData,
+            };
+            self.list.forget_nodes(&range);
+            range
+        }
+    }
+
+    pub fn move_range_to(&mut self, n: usize, other: &mut CursorMut<'_, T>) {
+        // the cut and the splice are both just pointer swaps
+        let range = self.remove_range(n);
+        other.splice_before(range);
+    }
+
+    pub fn replace_current(&mut self, elem: T) -> T {
+        let cur = self.current().expect("cannot replace the ghost element");
+        std::mem::replace(cur, elem)
+    }
+
+    pub fn splice_before(&mut self, mut input: LinkedList<T>) {
+        unsafe {
+            // we can either `take` the input's ptrs or `mem::forget`
+            // it. using take is more responsible in case we do custom
+            // allocators or something that also needs to be cleaned up
+            if input.is_empty() {
+                // do nothing
+            } else if let Some(cur) = self.cur {
+                // both lists are non-empty
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                if let Some(prev) = (*cur.as_ptr()).front {
+                    // general case, no boundaries, just internal fixups
+                    (*prev.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(prev);
+                    (*cur.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(cur);
+
+                } else {
+                    // we're appending to the front, see append to back
+                    (*cur.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(cur);
+                    self.list.front = Some(in_front);
+                }
+                // index moves forward by input length
+                self.index = self.index.map(|index| index + input.len);
+            } else if let Some(back) = self.list.back {
+                // we're on the ghost, but non-empty, append to the back
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                (*back.as_ptr()).back = Some(in_front);
+                (*in_front.as_ptr()).front = Some(back);
+                self.list.back = Some(in_back);
+            } else {
+                // we're empty, take the input's nodes, remain on the ghost
+                self.list.link_back(&mut input);
+            }
+            self.list.len += input.len;
+            input.len = 0;
+        }
+    }
+
+    pub fn splice_after(&mut self, mut input: LinkedList<T>) {
+        unsafe {
+            // we can either `take` the input's ptrs or `mem::forget`
+            // it. using take is more responsible in case we do custom
+            // allocators or something that also needs to be cleaned up
+            if input.is_empty() {
+                // do nothing
+            } else if let Some(cur) = self.cur {
+                // both lists are non-empty
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                if let Some(next) = (*cur.as_ptr()).back {
+                    // general case, no boundaries, just internal fixups
+                    (*next.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(next);
+                    (*cur.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(cur);
+
+                } else {
+                    // we're appending to the back, see append to front
+                    (*cur.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(cur);
+                    self.list.back = Some(in_back);
+                }
+            } else if let Some(front) = self.list.front {
+                // we're on the ghost, but non-empty, append to the front
+                let in_front = input.front.take().unwrap();
+                let in_back = input.back.take().unwrap();
+
+                (*front.as_ptr()).front = Some(in_back);
+                (*in_back.as_ptr()).back = Some(front);
+                self.list.front = Some(in_front);
+            } else {
+                // we're empty, take the input's nodes, remain on the ghost
+                self.list.link_back(&mut input);
+            }
+            self.list.len += input.len;
+            input.len = 0;
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+unsafe impl<'a, T: Send> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
+// a handle is useless without a borrow of its list, which is checked there
+unsafe impl<T: Send> Send for NodeHandle<T> {}
+unsafe impl<T: Sync> Sync for NodeHandle<T> {}
+
+unsafe impl<'a, T: Sync> Send for Pairs<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Pairs<'a, T> {}
+
+unsafe impl<'a, T: Send> Send for PairsMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for PairsMut<'a, T> {}
+
+unsafe impl<'a, T: Sync> Send for Cursor<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Cursor<'a, T> {}
+
+#[allow(dead_code)]
+fn assert_properties() {
+    fn is_send<T: Send>() {}
+    fn is_sync<T: Sync>() {}
+
+    is_send::<LinkedList<i32>>();
+    is_sync::<LinkedList<i32>>();
+
+    is_send::<IntoIter<i32>>();
+    is_sync::<IntoIter<i32>>();
+
+    is_send::<Iter<i32>>();
+    is_sync::<Iter<i32>>();
+
+    is_send::<IterMut<i32>>();
+    is_sync::<IterMut<i32>>();
+
+    is_send::<Cursor<i32>>();
+    is_sync::<Cursor<i32>>();
+
+    is_send::<NodeHandle<i32>>();
+    is_sync::<NodeHandle<i32>>();
+
+    fn linked_list_covariant<'a, T>(x: LinkedList<&'static T>) -> LinkedList<&'a T> { x }
+    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> { x }
+    fn into_iter_covariant<'a, T>(x: IntoIter<&'static T>) -> IntoIter<&'a T> { x }
+    fn cursor_covariant<'i, 'a, T>(x: Cursor<'i, &'static T>) -> Cursor<'i, &'a T> { x }
+}
+
+/// ```compile_fail
+/// use many_linked_lists::sixth::IterMut;
+/// fn iter_mut_covariant<'i, 'a, T>(x: IterMut<'i, &'static T>) -> IterMut<'i, &'a T> { x }
+/// ```
+#[allow(dead_code)]
+fn iter_mut_invariant() {}
+
+#[cfg(test)]
+mod test {
+    use super::LinkedList;
+
+    fn generate_test() -> LinkedList<i32> {
+        list_from(&[0, 1, 2, 3, 4, 5, 6])
+    }
+
+    fn list_from<T: Clone>(v: &[T]) -> LinkedList<T> {
+        v.iter().map(|x| (*x).clone()).collect()
+    }
+
+    #[test]
+    fn test_basic_front() {
+        let mut list = LinkedList::new();
+
+        // Try to break an empty list
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        // Try to break a one item list
+        list.push_front(10);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        // Mess around
+        list.push_front(10);
+        assert_eq!(list.len(), 1);
+        list.push_front(20);
+        assert_eq!(list.len(), 2);
+        list.push_front(30);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(30));
+        assert_eq!(list.len(), 2);
+        list.push_front(40);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(40));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_front(), Some(20));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut m = LinkedList::new();
+        assert_eq!(m.pop_front(), None);
+        assert_eq!(m.pop_back(), None);
+        assert_eq!(m.pop_front(), None);
+        m.push_front(1);
+        assert_eq!(m.pop_front(), Some(1));
+        m.push_back(2);
+        m.push_back(3);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.pop_front(), Some(2));
+        assert_eq!(m.pop_front(), Some(3));
+        assert_eq!(m.len(), 0);
+        assert_eq!(m.pop_front(), None);
+        m.push_back(1);
+        m.push_back(3);
+        m.push_back(5);
+        m.push_back(7);
+        assert_eq!(m.pop_front(), Some(1));
+
+        let mut n = LinkedList::new();
+        n.push_front(2);
+        n.push_front(3);
+        {
+            assert_eq!(n.front().unwrap(), &3);
+            let x = n.front_mut().unwrap();
+            assert_eq!(*x, 3);
+            *x = 0;
+        }
+        {
+            assert_eq!(n.back().unwrap(), &2);
+            let y = n.back_mut().unwrap();
+            assert_eq!(*y, 2);
+            *y = 1;
+        }
+        assert_eq!(n.pop_front(), Some(0));
+        assert_eq!(n.pop_front(), Some(1));
+    }
+
+    #[test]
+    fn test_iterator() {
+        let m = generate_test();
+        for (i, elt) in m.iter().enumerate() {
+            assert_eq!(i as i32, *elt);
+        }
+        let mut n = LinkedList::new();
+        assert_eq!(n.iter().next(), None);
+        n.push_front(4);
+        let mut it = n.iter();
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(it.next().unwrap(), &4);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_iterator_double_end() {
+        let mut n = LinkedList::new();
+        assert_eq!(n.iter().next(), None);
+        n.push_front(4);
+        n.push_front(5);
+        n.push_front(6);
+        let mut it = n.iter();
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next().unwrap(), &6);
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next_back().unwrap(), &4);
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(it.next_back().unwrap(), &5);
+        assert_eq!(it.next_back(), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_rev_iter() {
+        let m = generate_test();
+        for (i, elt) in m.iter().rev().enumerate() {
+            assert_eq!(6 - i as i32, *elt);
+        }
+        let mut n = LinkedList::new();
+        assert_eq!(n.iter().next_back(), None);
+        n.push_front(4);
+        let mut it = n.iter().rev();
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(it.next().unwrap(), &4);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_mut_iter() {
+        let mut m = generate_test();
+        let mut len = m.len();
+        for (i, elt) in m.iter_mut().enumerate() {
+            assert_eq!(i as i32, *elt);
+            len -= 1;
+        }
+        assert_eq!(len, 0);
+        let mut n = LinkedList::new();
+        assert!(n.iter_mut().next().is_none());
+        n.push_front(4);
+        n.push_back(5);
+        let mut it = n.iter_mut();
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert!(it.next().is_some());
+        assert!(it.next().is_some());
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_iterator_mut_double_end() {
+        let mut n = LinkedList::new();
+        assert!(n.iter_mut().next_back().is_none());
+        n.push_front(4);
+        n.push_front(5);
+        n.push_front(6);
+        let mut it = n.iter_mut();
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(*it.next().unwrap(), 6);
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(*it.next_back().unwrap(), 4);
+        assert_eq!(it.size_hint(), (1, Some(1)));
+        assert_eq!(*it.next_back().unwrap(), 5);
+        assert!(it.next_back().is_none());
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_iterator_specializations() {
+        let v: Vec<i32> = (0..9).collect();
+        let mut m = list_from(&v);
+
+        assert_eq!(m.iter().count(), 9);
+        assert_eq!(m.iter_mut().count(), 9);
+        assert_eq!(m.iter().last(), Some(&8));
+        assert_eq!(m.iter_mut().last(), Some(&mut 8));
+        assert_eq!(m.clone().into_iter().count(), 9);
+        assert_eq!(m.clone().into_iter().last(), Some(8));
+        assert_eq!(m.iter().fold(0, |acc, x| acc * 2 + x), v.iter().fold(0, |acc, x| acc * 2 + x));
+        assert_eq!(m.iter().rfold(0, |acc, x| acc * 2 + x), v.iter().rfold(0, |acc, x| acc * 2 + x));
+        m.iter_mut().fold((), |(), x| *x += 1);
+        m.iter_mut().rfold((), |(), x| *x -= 1);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), v);
+
+        // walk nth/nth_back through every mix of steps, against Vec's iter
+        for a in 0..4 {
+            for b in 0..4 {
+                let mut expected = v.iter();
+                let mut it = m.iter();
+                let mut into = m.clone().into_iter();
+                for _ in 0..3 {
+                    assert_eq!(it.nth(a), expected.clone().nth(a));
+                    assert_eq!(into.nth(a).as_ref(), expected.nth(a));
+                    assert_eq!(it.nth_back(b), expected.clone().nth_back(b));
+                    assert_eq!(into.nth_back(b).as_ref(), expected.nth_back(b));
+                    assert_eq!(it.len(), expected.len());
+                    assert_eq!(into.len(), expected.len());
+                }
+                assert_eq!(it.clone().collect::<Vec<_>>(), expected.clone().collect::<Vec<_>>());
+                assert_eq!(into.collect::<Vec<_>>(), expected.cloned().collect::<Vec<_>>());
+            }
+        }
+
+        let mut it = m.iter_mut();
+        assert_eq!(it.nth(2), Some(&mut 2));
+        assert_eq!(it.nth_back(2), Some(&mut 6));
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.nth(5), None);
+        assert_eq!(it.next(), None);
+        assert_eq!(it.next_back(), None);
+    }
+
+    #[test]
+    fn test_iterator_debug_clone() {
+        let mut m = list_from(&[1, 2, 3]);
+        let mut it = m.iter();
+        it.next();
+        let copy = it.clone();
+        assert_eq!(it.next(), Some(&2));
+        assert_eq!(format!("{:?}", copy), "Iter([2, 3])");
+        assert_eq!(format!("{:?}", it), "Iter([3])");
+
+        let mut it = m.iter_mut();
+        it.next_back();
+        assert_eq!(format!("{:?}", it), "IterMut([1, 2])");
+
+        let mut it = m.into_iter();
+        it.next();
+        assert_eq!(format!("{:?}", it), "IntoIter([2, 3])");
+    }
+
+    #[test]
+    fn test_iter_range() {
+        let mut m: LinkedList<i32> = (0..8).collect();
+        assert_eq!(m.iter_range(2..5).cloned().collect::<Vec<_>>(), &[2, 3, 4]);
+        assert_eq!(m.iter_range(..3).rev().cloned().collect::<Vec<_>>(), &[2, 1, 0]);
+        assert_eq!(m.iter_range(6..).len(), 2);
+        assert_eq!(m.iter_range(3..3).next(), None);
+        assert_eq!(m.iter_range(0..0).next_back(), None);
+        assert_eq!(m.iter_range(..).count(), 8);
+        assert_eq!(m.iter_range(7..=7).last(), Some(&7));
+
+        for x in m.iter_range_mut(1..=2) {
+            *x *= 10;
+        }
+        m.iter_range_mut(5..).rev().for_each(|x| *x = -*x);
+        assert_eq!(m, [0, 10, 20, 3, 4, -5, -6, -7]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 9 out of range for slice of length 8")]
+    fn test_iter_range_out_of_bounds() {
+        let m: LinkedList<i32> = (0..8).collect();
+        m.iter_range(2..9);
+    }
+
+    #[test]
+    fn test_iter_mut_split() {
+        let mut m: LinkedList<i32> = (0..10).collect();
+        for at in 0..=10 {
+            let (front, back) = m.iter_mut_split(at);
+            assert_eq!(front.len(), at);
+            assert_eq!(back.len(), 10 - at);
+            assert_eq!(front.map(|x| *x).collect::<Vec<_>>(), (0..at as i32).collect::<Vec<_>>());
+            assert_eq!(back.rev().map(|x| *x).collect::<Vec<_>>(), (at as i32..10).rev().collect::<Vec<_>>());
+        }
+
+        // the halves are disjoint, so they can go to different threads
+        let (front, back) = m.iter_mut_split(4);
+        std::thread::scope(|s| {
+            s.spawn(|| front.for_each(|x| *x += 100));
+            s.spawn(|| back.for_each(|x| *x -= 100));
+        });
+        check_links(&m);
+        assert_eq!(m, [100, 101, 102, 103, -96, -95, -94, -93, -92, -91]);
+    }
+
+    #[test]
+    fn test_pairs() {
+        let m: LinkedList<i32> = LinkedList::new();
+        assert_eq!(m.pairs().next(), None);
+        let m = list_from(&[1]);
+        assert_eq!(m.pairs().len(), 0);
+        assert_eq!(m.pairs().next_back(), None);
+
+        let m = list_from(&[1, 2, 4, 7]);
+        let mut pairs = m.pairs();
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs.next(), Some((&1, &2)));
+        assert_eq!(pairs.next_back(), Some((&4, &7)));
+        assert_eq!(pairs.next(), Some((&2, &4)));
+        assert_eq!(pairs.next(), None);
+        assert_eq!(pairs.next_back(), None);
+
+        let deltas: Vec<i32> = m.pairs().map(|(a, b)| b - a).collect();
+        assert_eq!(deltas, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_pairs_mut() {
+        let mut m = list_from(&[10, 12, 15, 15, 20]);
+
+        // delta-encode in place, back to front so each `prev` is still raw
+        let mut pairs = m.pairs_mut();
+        assert_eq!(pairs.len(), 4);
+        while let Some((prev, cur)) = pairs.next_pair_back() {
+            *cur -= *prev;
+        }
+        assert!(pairs.is_empty());
+        assert_eq!(m, [10, 2, 3, 0, 5]);
+
+        // and decode it again front to back
+        let mut pairs = m.pairs_mut();
+        while let Some((prev, cur)) = pairs.next_pair() {
+            *cur += *prev;
+        }
+        assert_eq!(pairs.next_pair_back(), None);
+        assert_eq!(m, [10, 12, 15, 15, 20]);
+
+        let mut m = list_from(&[1]);
+        assert!(m.pairs_mut().next_pair().is_none());
+    }
+
+    #[test]
+    fn test_eq() {
+        let mut n: LinkedList<u8> = list_from(&[]);
+        let mut m = list_from(&[]);
+        assert!(n == m);
+        n.push_front(1);
+        assert!(n != m);
+        m.push_back(1);
+        assert!(n == m);
+
+        let n = list_from(&[2, 3, 4]);
+        let m = list_from(&[1, 2, 3]);
+        assert!(n != m);
+    }
+
+    #[test]
+    fn test_linked_list_macro() {
+        let m = linked_list![1, 2, 3];
+        check_links(&m);
+        assert_eq!(m, [1, 2, 3]);
+        let m = linked_list![4, 5,];
+        assert_eq!(m, [4, 5]);
+        let m: LinkedList<i32> = linked_list![];
+        assert!(m.is_empty());
+
+        let m = linked_list![vec![1]; 3];
+        check_links(&m);
+        assert_eq!(m, [vec![1], vec![1], vec![1]]);
+        let m = linked_list![0; 0];
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn test_std_conversions() {
+        use std::collections::{self, VecDeque};
+
+        let m = LinkedList::from(vec![1, 2, 3]);
+        check_links(&m);
+        assert_eq!(m, vec![1, 2, 3]);
+        assert_eq!(m, [1, 2, 3]);
+        assert_eq!(m, [1, 2, 3][..]);
+        assert!(m != [1, 2]);
+        assert!(m != vec![1, 2, 4]);
+
+        assert_eq!(LinkedList::from([4, 5]), [4, 5]);
+        assert_eq!(LinkedList::<i32>::from([]), []);
+        assert_eq!(LinkedList::from(VecDeque::from(vec![6, 7])), [6, 7]);
+        let std_list: collections::LinkedList<i32> = (0..3).collect();
+        assert_eq!(LinkedList::from(std_list), [0, 1, 2]);
+
+        let v: Vec<i32> = m.clone().into();
+        assert_eq!(v, &[1, 2, 3]);
+        let d: VecDeque<i32> = m.clone().into();
+        assert_eq!(d, &[1, 2, 3]);
+        let l: collections::LinkedList<i32> = m.clone().into();
+        assert!(l.iter().eq(&[1, 2, 3]));
+
+        let mut m = m;
+        m.extend(&[4, 5]);
+        m.extend([6].iter());
+        check_links(&m);
+        assert_eq!(m, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_ord() {
+        let n = list_from(&[]);
+        let m = list_from(&[1, 2, 3]);
+        assert!(n < m);
+        assert!(m > n);
+        assert!(n <= n);
+        assert!(n >= n);
+    }
+
+    #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn test_ord_nan() {
+        let nan = f64::NAN;
+        let n = list_from(&[nan]);
+        let m = list_from(&[nan]);
+        assert!(!(n < m));
+        assert!(!(n > m));
+        assert!(!(n <= m));
+        assert!(!(n >= m));
+
+        let n = list_from(&[nan]);
+        let one = list_from(&[1.0f64]);
+        assert!(!(n < one));
+        assert!(!(n > one));
+        assert!(!(n <= one));
+        assert!(!(n >= one));
+
+        let u = list_from(&[1.0f64, 2.0, nan]);
+        let v = list_from(&[1.0f64, 2.0, 3.0]);
+        assert!(!(u < v));
+        assert!(!(u > v));
+        assert!(!(u <= v));
+        assert!(!(u >= v));
+
+        let s = list_from(&[1.0f64, 2.0, 4.0, 2.0]);
+        let t = list_from(&[1.0f64, 2.0, 3.0, 2.0]);
+        assert!(!(s < t));
+        assert!(s > one);
+        assert!(!(s <= one));
+        assert!(s >= one);
+    }
+
+    #[test]
+    fn test_debug() {
+        let list: LinkedList<i32> = (0..10).collect();
+        assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+
+        let list: LinkedList<&str> = ["just", "one", "test", "more"]
+            .iter().copied()
+            .collect();
+        assert_eq!(format!("{:?}", list), r#"["just", "one", "test", "more"]"#);
+    }
+
+    #[test]
+    fn test_hashmap() {
+        // Check that HashMap works with this as a key
+
+        let list1: LinkedList<i32> = (0..10).collect();
+        let list2: LinkedList<i32> = (1..11).collect();
+        let mut map = std::collections::HashMap::new();
+
+        assert_eq!(map.insert(list1.clone(), "list1"), None);
+        assert_eq!(map.insert(list2.clone(), "list2"), None);
+
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.get(&list1), Some(&"list1"));
+        assert_eq!(map.get(&list2), Some(&"list2"));
+
+        assert_eq!(map.remove(&list1), Some("list1"));
+        assert_eq!(map.remove(&list2), Some("list2"));
+
+        assert!(map.is_empty());
+    }
+    #[test]
+    fn test_cursor_move_peek() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.peek_next(), Some(&mut 3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        assert_eq!(cursor.index(), Some(1));
+
+        let mut cursor = m.cursor_mut();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 6));
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(5));
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.peek_next(), Some(&mut 6));
+        assert_eq!(cursor.peek_prev(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(4));
+    }
+
+    #[test]
+    fn test_cursor_move_peek_shared() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_front();
+        let other = m.cursor_back();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.peek_next(), Some(&2));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&6));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&2));
+        assert_eq!(cursor.peek_next(), Some(&3));
+        assert_eq!(cursor.peek_prev(), Some(&1));
+        assert_eq!(cursor.index(), Some(1));
+
+        // both cursors are usable at once
+        let mut other = other.clone();
+        assert_eq!(other.current(), Some(&6));
+        assert_eq!(other.index(), Some(5));
+        other.move_next();
+        assert_eq!(other.current(), None);
+        assert_eq!(other.index(), None);
+        assert_eq!(cursor.current(), Some(&2));
+
+        let empty: LinkedList<u32> = LinkedList::new();
+        assert_eq!(empty.cursor_front().current(), None);
+        assert_eq!(empty.cursor_back().index(), None);
+
+        let mut cursor = m.cursor_mut();
+        cursor.move_prev();
+        cursor.move_prev();
+        let shared = cursor.as_cursor();
+        assert_eq!(shared.current(), Some(&5));
+        assert_eq!(shared.peek_next(), Some(&6));
+        assert_eq!(shared.index(), Some(4));
+    }
+
+    #[test]
+    fn test_cursor_mut_seek() {
+        let mut m: LinkedList<i32> = (0..10).collect();
+        let mut cursor = m.cursor_mut();
+        for &i in &[3, 8, 7, 0, 9, 4, 4, 5, 1] {
+            cursor.seek(i);
+            assert_eq!(cursor.index(), Some(i));
+            assert_eq!(cursor.current(), Some(&mut (i as i32)));
+        }
+        cursor.seek(10);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        cursor.seek(2);
+        assert_eq!(cursor.current(), Some(&mut 2));
+
+        let cursor = m.cursor_mut_at(6);
+        assert_eq!(cursor.index(), Some(6));
+        assert_eq!(cursor.as_cursor().current(), Some(&6));
+        assert_eq!(m.cursor_mut_at(42).index(), None);
+
+        // seeking from a handle cursor still lands in the right spot
+        let h = m.push_back_handle(10);
+        let mut cursor = m.cursor_at(&h).unwrap();
+        cursor.seek(9);
+        assert_eq!(cursor.current(), Some(&mut 9));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        let mut cursor = empty.cursor_mut_at(0);
+        assert_eq!(cursor.current(), None);
+        cursor.seek(0);
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn test_cursor_mut_find() {
+        let mut m: LinkedList<i32> = list_from(&[5, 3, 8, 3, 1]);
+        let mut cursor = m.cursor_mut_find(|&x| x == 3);
+        assert_eq!(cursor.index(), Some(1));
+        cursor.insert_before(4);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[5, 4, 3, 8, 3, 1]);
+
+        let mut cursor = m.cursor_mut_find(|&x| x > 100);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        assert_eq!(empty.cursor_mut_find(|_| true).index(), None);
+    }
+
+    #[test]
+    fn test_cursor_mut_insert() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.splice_before(Some(7).into_iter().collect());
+        cursor.splice_after(Some(8).into_iter().collect());
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[7, 1, 8, 2, 3, 4, 5, 6]);
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_prev();
+        cursor.splice_before(Some(9).into_iter().collect());
+        cursor.splice_after(Some(10).into_iter().collect());
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[10, 7, 1, 8, 2, 3, 4, 5, 6, 9]);
+
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(7));
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), Some(9));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(10));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 8, 2, 3, 4, 5, 6]);
+
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.extend([1, 8, 2, 3, 4, 5, 6]);
+
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        let mut p: LinkedList<u32> = LinkedList::new();
+        p.extend([100, 101, 102, 103]);
+        let mut q: LinkedList<u32> = LinkedList::new();
+        q.extend([200, 201, 202, 203]);
+        cursor.splice_after(p);
+        cursor.splice_before(q);
+        check_links(&m);
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[200, 201, 202, 203, 1, 100, 101, 102, 103, 8, 2, 3, 4, 5, 6]
+        );
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_prev();
+        let tmp = cursor.split_before();
+        assert_eq!(m.into_iter().collect::<Vec<_>>(), &[]);
+        m = tmp;
+        let mut cursor = m.cursor_mut();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        let tmp = cursor.split_after();
+        assert_eq!(tmp.into_iter().collect::<Vec<_>>(), &[102, 103, 8, 2, 3, 4, 5, 6]);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[200, 201, 202, 203, 1, 100, 101]);
     }
-}
-
-unsafe impl<T: Send> Send for LinkedList<T> {}
-unsafe impl<T: Sync> Sync for LinkedList<T> {}
-
-unsafe impl<'a, T: Send> Send for Iter<'a, T> {}
-unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
 
-unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
-unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+    #[test]
+    fn test_append_prepend() {
+        let mut m = list_from(&[1, 2, 3]);
+        let mut n = list_from(&[4, 5]);
+        m.append(&mut n);
+        check_links(&m);
+        check_links(&n);
+        assert!(n.is_empty());
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2, 3, 4, 5]);
+
+        let mut e = LinkedList::new();
+        m.append(&mut e);
+        assert_eq!(m.len(), 5);
+        e.append(&mut m);
+        check_links(&e);
+        assert!(m.is_empty());
+        assert_eq!(e.len(), 5);
+
+        let mut p = list_from(&[-1, 0]);
+        e.prepend(&mut p);
+        check_links(&e);
+        check_links(&p);
+        assert!(p.is_empty());
+        assert_eq!(e.iter().cloned().collect::<Vec<_>>(), &[-1, 0, 1, 2, 3, 4, 5]);
+
+        p.prepend(&mut e);
+        check_links(&p);
+        assert_eq!(p.len(), 7);
+        assert!(e.is_empty());
+    }
 
-#[allow(dead_code)]
-fn assert_properties() {
-    fn is_send<T: Send>() {}
-    fn is_sync<T: Sync>() {}
+    #[test]
+    fn test_split_off() {
+        let v: Vec<i32> = (0..9).collect();
+        for at in 0..=v.len() {
+            let mut m = list_from(&v);
+            let n = m.split_off(at);
+            check_links(&m);
+            check_links(&n);
+            assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &v[..at]);
+            assert_eq!(n.iter().cloned().collect::<Vec<_>>(), &v[at..]);
+        }
+    }
 
-    is_send::<LinkedList<i32>>();
-    is_sync::<LinkedList<i32>>();
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        let mut m = list_from(&[1, 2, 3]);
+        m.split_off(4);
+    }
 
-    is_send::<IntoIter<i32>>();
-    is_sync::<IntoIter<i32>>();
+    #[test]
+    fn test_truncate() {
+        let v: Vec<i32> = (0..9).collect();
+        for len in 0..=v.len() + 1 {
+            let mut m = list_from(&v);
+            m.truncate(len);
+            check_links(&m);
+            assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &v[..len.min(v.len())]);
+
+            let mut m = list_from(&v);
+            m.truncate_front(len);
+            check_links(&m);
+            assert_eq!(
+                m.iter().cloned().collect::<Vec<_>>(),
+                &v[v.len() - len.min(v.len())..]
+            );
+        }
+    }
 
-    is_send::<Iter<i32>>();
-    is_sync::<Iter<i32>>();
+    #[test]
+    fn test_positional() {
+        let mut m = generate_test();
+        for i in 0..7 {
+            assert_eq!(m.get(i), Some(&(i as i32)));
+            assert_eq!(m[i], i as i32);
+        }
+        assert_eq!(m.get(7), None);
+        assert_eq!(m.get_mut(7), None);
 
-    is_send::<IterMut<i32>>();
-    is_sync::<IterMut<i32>>();
+        *m.get_mut(5).unwrap() = 50;
+        m[1] = 10;
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 10, 2, 3, 4, 50, 6]);
 
-    fn linked_list_covariant<'a, T>(x: LinkedList<&'static T>) -> LinkedList<&'a T> { x }
-    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> { x }
-    fn into_iter_covariant<'a, T>(x: IntoIter<&'static T>) -> IntoIter<&'a T> { x }
-}
+        m.insert(0, -1);
+        m.insert(8, 7);
+        m.insert(4, 100);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[-1, 0, 10, 2, 100, 3, 4, 50, 6, 7]);
+        assert_eq!(m.try_insert(11, 8), Err(8));
 
-/// ```compile_fail
-/// use many_linked_lists::sixth::IterMut;
-/// fn iter_mut_covariant<'i, 'a, T>(x: IterMut<'i, &'static T>) -> IterMut<'i, &'a T> { x }
-/// ```
-#[allow(dead_code)]
-fn iter_mut_invariant() {}
+        assert_eq!(m.remove(4), 100);
+        assert_eq!(m.remove(0), -1);
+        assert_eq!(m.remove(7), 7);
+        assert_eq!(m.try_remove(7), None);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 10, 2, 3, 4, 50, 6]);
 
-#[cfg(test)]
-mod test {
-    use super::LinkedList;
+        m.swap(0, 6);
+        m.swap(2, 2);
+        m.swap(5, 1);
+        assert_eq!(m.try_swap(0, 7), None);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[6, 50, 2, 3, 4, 10, 0]);
+    }
 
-    fn generate_test() -> LinkedList<i32> {
-        list_from(&[0, 1, 2, 3, 4, 5, 6])
+    #[test]
+    #[should_panic(expected = "removal index (is 3) should be < len (is 3)")]
+    fn test_remove_out_of_bounds() {
+        let mut m = list_from(&[1, 2, 3]);
+        m.remove(3);
     }
 
-    fn list_from<T: Clone>(v: &[T]) -> LinkedList<T> {
-        v.iter().map(|x| (*x).clone()).collect()
+    #[test]
+    #[should_panic(expected = "insertion index (is 4) should be <= len (is 3)")]
+    fn test_insert_out_of_bounds() {
+        let mut m = list_from(&[1, 2, 3]);
+        m.insert(4, 0);
     }
 
     #[test]
-    fn test_basic_front() {
-        let mut list = LinkedList::new();
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds() {
+        let m = list_from(&[1, 2, 3]);
+        let _ = m[3];
+    }
 
-        // Try to break an empty list
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
+    #[test]
+    fn test_retain() {
+        let mut m: LinkedList<i32> = (0..10).collect();
+        m.retain(|&x| x % 3 != 0);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2, 4, 5, 7, 8]);
 
-        // Try to break a one item list
-        list.push_front(10);
-        assert_eq!(list.len(), 1);
-        assert_eq!(list.pop_front(), Some(10));
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
+        m.retain_mut(|x| {
+            *x *= 10;
+            *x > 40
+        });
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[50, 70, 80]);
 
-        // Mess around
-        list.push_front(10);
-        assert_eq!(list.len(), 1);
-        list.push_front(20);
-        assert_eq!(list.len(), 2);
-        list.push_front(30);
-        assert_eq!(list.len(), 3);
-        assert_eq!(list.pop_front(), Some(30));
-        assert_eq!(list.len(), 2);
-        list.push_front(40);
-        assert_eq!(list.len(), 3);
-        assert_eq!(list.pop_front(), Some(40));
-        assert_eq!(list.len(), 2);
-        assert_eq!(list.pop_front(), Some(20));
-        assert_eq!(list.len(), 1);
-        assert_eq!(list.pop_front(), Some(10));
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
-        assert_eq!(list.pop_front(), None);
-        assert_eq!(list.len(), 0);
+        m.retain(|_| false);
+        check_links(&m);
+        assert!(m.is_empty());
     }
 
     #[test]
-    fn test_basic() {
-        let mut m = LinkedList::new();
-        assert_eq!(m.pop_front(), None);
-        assert_eq!(m.pop_back(), None);
-        assert_eq!(m.pop_front(), None);
-        m.push_front(1);
-        assert_eq!(m.pop_front(), Some(1));
-        m.push_back(2);
-        m.push_back(3);
-        assert_eq!(m.len(), 2);
-        assert_eq!(m.pop_front(), Some(2));
-        assert_eq!(m.pop_front(), Some(3));
-        assert_eq!(m.len(), 0);
-        assert_eq!(m.pop_front(), None);
-        m.push_back(1);
-        m.push_back(3);
-        m.push_back(5);
-        m.push_back(7);
-        assert_eq!(m.pop_front(), Some(1));
+    fn test_extract_if() {
+        let mut m: LinkedList<i32> = (0..10).collect();
+        let evens: Vec<_> = m.extract_if(|x| *x % 2 == 0).collect();
+        check_links(&m);
+        assert_eq!(evens, &[0, 2, 4, 6, 8]);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 3, 5, 7, 9]);
 
-        let mut n = LinkedList::new();
-        n.push_front(2);
-        n.push_front(3);
+        // dropping it part way leaves everything after the last match alone
+        let mut m: LinkedList<i32> = (0..10).collect();
         {
-            assert_eq!(n.front().unwrap(), &3);
-            let x = n.front_mut().unwrap();
-            assert_eq!(*x, 3);
-            *x = 0;
+            let mut iter = m.extract_if(|x| *x > 2 && *x % 2 == 1);
+            assert_eq!(iter.next(), Some(3));
+            assert_eq!(iter.next(), Some(5));
         }
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 1, 2, 4, 6, 7, 8, 9]);
+
+        let mut m: LinkedList<i32> = (0..3).collect();
         {
-            assert_eq!(n.back().unwrap(), &2);
-            let y = n.back_mut().unwrap();
-            assert_eq!(*y, 2);
-            *y = 1;
+            let mut iter = m.extract_if(|_| true);
+            assert_eq!(iter.size_hint(), (0, Some(3)));
+            assert_eq!(iter.next(), Some(0));
+            assert_eq!(iter.next(), Some(1));
+            assert_eq!(iter.next(), Some(2));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next(), None);
         }
-        assert_eq!(n.pop_front(), Some(0));
-        assert_eq!(n.pop_front(), Some(1));
+        check_links(&m);
+        assert!(m.is_empty());
     }
 
     #[test]
-    fn test_iterator() {
-        let m = generate_test();
-        for (i, elt) in m.iter().enumerate() {
-            assert_eq!(i as i32, *elt);
+    fn test_drain() {
+        let v: Vec<i32> = (0..8).collect();
+        for start in 0..=v.len() {
+            for end in start..=v.len() {
+                let mut m = list_from(&v);
+                let drained: Vec<_> = m.drain(start..end).collect();
+                check_links(&m);
+                assert_eq!(drained, &v[start..end]);
+                let mut expected = v.clone();
+                expected.drain(start..end);
+                assert_eq!(m.iter().cloned().collect::<Vec<_>>(), expected);
+            }
         }
-        let mut n = LinkedList::new();
-        assert_eq!(n.iter().next(), None);
-        n.push_front(4);
-        let mut it = n.iter();
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(it.next().unwrap(), &4);
-        assert_eq!(it.size_hint(), (0, Some(0)));
-        assert_eq!(it.next(), None);
+
+        let mut m = list_from(&v);
+        assert_eq!(m.drain(..).len(), 8);
+        assert!(m.is_empty());
+
+        let mut m = list_from(&v);
+        let mut drain = m.drain(2..=5);
+        assert_eq!(drain.len(), 4);
+        assert_eq!(drain.next(), Some(2));
+        assert_eq!(drain.next_back(), Some(5));
+        assert_eq!(drain.len(), 2);
+        drop(drain);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 1, 6, 7]);
+
+        // forgetting the drain still leaves a consistent list
+        let mut m = list_from(&v);
+        std::mem::forget(m.drain(1..3));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 3, 4, 5, 6, 7]);
     }
 
     #[test]
-    fn test_iterator_double_end() {
-        let mut n = LinkedList::new();
-        assert_eq!(n.iter().next(), None);
-        n.push_front(4);
-        n.push_front(5);
-        n.push_front(6);
-        let mut it = n.iter();
-        assert_eq!(it.size_hint(), (3, Some(3)));
-        assert_eq!(it.next().unwrap(), &6);
-        assert_eq!(it.size_hint(), (2, Some(2)));
-        assert_eq!(it.next_back().unwrap(), &4);
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(it.next_back().unwrap(), &5);
-        assert_eq!(it.next_back(), None);
-        assert_eq!(it.next(), None);
+    #[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+    fn test_drain_out_of_bounds() {
+        let mut m = list_from(&[1, 2, 3]);
+        m.drain(1..4);
     }
 
     #[test]
-    fn test_rev_iter() {
-        let m = generate_test();
-        for (i, elt) in m.iter().rev().enumerate() {
-            assert_eq!(6 - i as i32, *elt);
+    fn test_drain_keeps_handles() {
+        let len = 6;
+        for start in 0..=len {
+            for end in start..=len {
+                let mut m: LinkedList<usize> = LinkedList::new();
+                let handles: Vec<_> = (0..len).map(|x| m.push_back_handle(x)).collect();
+                assert_eq!(m.drain(start..end).count(), end - start);
+                check_links(&m);
+                for (i, h) in handles.iter().enumerate() {
+                    if (start..end).contains(&i) {
+                        assert_eq!(m.get_by_handle(h), None);
+                    } else {
+                        assert_eq!(m.get_by_handle(h), Some(&i));
+                    }
+                }
+                // and they still remove the right node
+                if let Some(i) = (0..len).find(|i| !(start..end).contains(i)) {
+                    assert_eq!(m.remove_by_handle(&handles[i]), Some(i));
+                    check_links(&m);
+                }
+            }
         }
-        let mut n = LinkedList::new();
-        assert_eq!(n.iter().rev().next(), None);
-        n.push_front(4);
-        let mut it = n.iter().rev();
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(it.next().unwrap(), &4);
-        assert_eq!(it.size_hint(), (0, Some(0)));
-        assert_eq!(it.next(), None);
     }
 
     #[test]
-    fn test_mut_iter() {
+    fn test_partition() {
+        let m: LinkedList<i32> = (0..10).collect();
+        let (evens, odds) = m.partition(|x| x % 2 == 0);
+        check_links(&evens);
+        check_links(&odds);
+        assert_eq!(evens.iter().cloned().collect::<Vec<_>>(), &[0, 2, 4, 6, 8]);
+        assert_eq!(odds.iter().cloned().collect::<Vec<_>>(), &[1, 3, 5, 7, 9]);
+
+        let m: LinkedList<i32> = (0..3).collect();
+        let (all, none) = m.partition(|_| true);
+        check_links(&all);
+        assert_eq!(all.len(), 3);
+        assert!(none.is_empty());
+
+        // the same nodes come out the other side
+        let m: LinkedList<i32> = (0..4).collect();
+        let addrs: Vec<*const i32> = m.iter().map(|x| x as *const i32).collect();
+        let (low, high) = m.partition(|&x| x < 2);
+        let moved: Vec<*const i32> = low.iter().chain(high.iter()).map(|x| x as *const i32).collect();
+        assert_eq!(addrs, moved);
+    }
+
+    #[test]
+    fn test_split_into_chunks() {
+        let m: LinkedList<i32> = (0..10).collect();
+        let chunks = m.split_into_chunks(3);
+        assert_eq!(chunks.len(), 4);
+        for chunk in &chunks {
+            check_links(chunk);
+        }
+        let chunks: Vec<Vec<i32>> = chunks.into_iter().map(|c| c.into_iter().collect()).collect();
+        assert_eq!(chunks, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8], vec![9]]);
+
+        let m: LinkedList<i32> = (0..4).collect();
+        assert_eq!(m.split_into_chunks(10).len(), 1);
+
+        let m: LinkedList<i32> = LinkedList::new();
+        assert!(m.split_into_chunks(2).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_split_into_zero_chunks() {
+        let m: LinkedList<i32> = (0..4).collect();
+        m.split_into_chunks(0);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut m: LinkedList<i32> = LinkedList::new();
+        m.reverse();
+        check_links(&m);
+        assert!(m.is_empty());
+
+        m.push_back(1);
+        m.reverse();
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1]);
+
         let mut m = generate_test();
-        let mut len = m.len();
-        for (i, elt) in m.iter_mut().enumerate() {
-            assert_eq!(i as i32, *elt);
-            len -= 1;
+        m.reverse();
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[6, 5, 4, 3, 2, 1, 0]);
+        m.push_front(7);
+        m.push_back(-1);
+        m.reverse();
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[-1, 0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let v: Vec<i32> = (0..7).collect();
+        for n in 0..=v.len() {
+            let mut m = list_from(&v);
+            m.rotate_left(n);
+            check_links(&m);
+            let mut expected = v.clone();
+            expected.rotate_left(n);
+            assert_eq!(m.iter().cloned().collect::<Vec<_>>(), expected);
+
+            let mut m = list_from(&v);
+            m.rotate_right(n);
+            check_links(&m);
+            let mut expected = v.clone();
+            expected.rotate_right(n);
+            assert_eq!(m.iter().cloned().collect::<Vec<_>>(), expected);
         }
-        assert_eq!(len, 0);
-        let mut n = LinkedList::new();
-        assert!(n.iter_mut().next().is_none());
-        n.push_front(4);
-        n.push_back(5);
-        let mut it = n.iter_mut();
-        assert_eq!(it.size_hint(), (2, Some(2)));
-        assert!(it.next().is_some());
-        assert!(it.next().is_some());
-        assert_eq!(it.size_hint(), (0, Some(0)));
-        assert!(it.next().is_none());
+
+        let mut m: LinkedList<i32> = LinkedList::new();
+        m.rotate_left(0);
+        m.rotate_right(0);
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rotate_too_far() {
+        let mut m = list_from(&[1, 2, 3]);
+        m.rotate_left(4);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut m = list_from(&[1, 3, 5, 7]);
+        let mut n = list_from(&[0, 2, 3, 8, 9]);
+        m.merge(&mut n);
+        check_links(&m);
+        check_links(&n);
+        assert!(n.is_empty());
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 1, 2, 3, 3, 5, 7, 8, 9]);
+
+        let mut e = LinkedList::new();
+        e.merge(&mut m);
+        check_links(&e);
+        assert!(m.is_empty());
+        assert_eq!(e.len(), 9);
+        e.merge(&mut m);
+        assert_eq!(e.len(), 9);
+
+        // ties keep self's elements first, and each side's own order
+        let mut rng = Rng(0x1234_5678_9abc_def0);
+        let mut a: Vec<(u32, usize)> = (0..200).map(|i| (rng.below(20) as u32, i)).collect();
+        let mut b: Vec<(u32, usize)> = (200..350).map(|i| (rng.below(20) as u32, i)).collect();
+        a.sort_by_key(|&(key, _)| key);
+        b.sort_by_key(|&(key, _)| key);
+        let mut m = list_from(&a);
+        let mut n = list_from(&b);
+        m.merge_by(&mut n, |x, y| x.0.cmp(&y.0));
+        check_links(&m);
+        assert!(n.is_empty());
+        let mut expected = a.clone();
+        expected.extend(b);
+        expected.sort_by_key(|&(key, _)| key);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut m = LinkedList::new();
+        for x in [5, 1, 4, 1, 9, 0, 9, 3] {
+            m.insert_sorted(x);
+            check_links(&m);
+            assert!(m.is_sorted());
+        }
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 1, 1, 3, 4, 5, 9, 9]);
+
+        // lands in front of equal elements
+        let mut m = list_from(&[(1, 'a'), (2, 'a'), (3, 'a')]);
+        m.insert_sorted_by((2, 'b'), |x, y| x.0.cmp(&y.0));
+        check_links(&m);
+        assert_eq!(
+            m.iter().cloned().collect::<Vec<_>>(),
+            &[(1, 'a'), (2, 'b'), (2, 'a'), (3, 'a')]
+        );
     }
 
     #[test]
-    fn test_iterator_mut_double_end() {
-        let mut n = LinkedList::new();
-        assert!(n.iter_mut().next_back().is_none());
-        n.push_front(4);
-        n.push_front(5);
-        n.push_front(6);
-        let mut it = n.iter_mut();
-        assert_eq!(it.size_hint(), (3, Some(3)));
-        assert_eq!(*it.next().unwrap(), 6);
-        assert_eq!(it.size_hint(), (2, Some(2)));
-        assert_eq!(*it.next_back().unwrap(), 4);
-        assert_eq!(it.size_hint(), (1, Some(1)));
-        assert_eq!(*it.next_back().unwrap(), 5);
-        assert!(it.next_back().is_none());
-        assert!(it.next().is_none());
+    fn test_dedup() {
+        let mut m: LinkedList<i32> = LinkedList::new();
+        m.dedup();
+        assert!(m.is_empty());
+
+        let mut m = list_from(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        m.dedup();
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2, 3, 1, 4]);
+
+        let mut m = list_from(&[7, 7, 7]);
+        m.dedup();
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[7]);
+
+        let mut m = list_from(&[10, 11, 20, 21, 22, 30, 13]);
+        m.dedup_by_key(|x| *x / 10);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[10, 20, 30, 13]);
+
+        // same_bucket sees (candidate, kept) and can fold into the keeper
+        let mut m = list_from(&[1, 2, 3, 10, 11, 1]);
+        m.dedup_by(|a, b| {
+            if (*a < 10) == (*b < 10) {
+                *b += *a;
+                true
+            } else {
+                false
+            }
+        });
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[6, 21, 1]);
     }
 
     #[test]
-    fn test_eq() {
-        let mut n: LinkedList<u8> = list_from(&[]);
-        let mut m = list_from(&[]);
-        assert!(n == m);
-        n.push_front(1);
-        assert!(n != m);
+    fn test_sort() {
+        let mut m: LinkedList<i32> = LinkedList::new();
+        m.sort();
+        assert!(m.is_sorted());
         m.push_back(1);
-        assert!(n == m);
+        m.sort();
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1]);
+
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for len in [2, 3, 7, 16, 33, 100, 1000] {
+            let v: Vec<i32> = (0..len).map(|_| rng.below(50) as i32).collect();
+            let mut m = list_from(&v);
+            assert_eq!(m.is_sorted(), v.is_sorted());
+            m.sort();
+            check_links(&m);
+            assert!(m.is_sorted());
+            let mut expected = v.clone();
+            expected.sort();
+            assert_eq!(m.iter().cloned().collect::<Vec<_>>(), expected);
+
+            m.sort_by(|a, b| b.cmp(a));
+            check_links(&m);
+            expected.reverse();
+            assert_eq!(m.iter().cloned().collect::<Vec<_>>(), expected);
+        }
+    }
 
-        let n = list_from(&[2, 3, 4]);
-        let m = list_from(&[1, 2, 3]);
-        assert!(n != m);
+    #[test]
+    fn test_sort_stable() {
+        let mut rng = Rng(0xdead_beef_cafe_f00d);
+        let v: Vec<(u32, usize)> = (0..500).map(|i| (rng.below(10) as u32, i)).collect();
+        let mut m = list_from(&v);
+        m.sort_by_key(|&(key, _)| key);
+        check_links(&m);
+
+        let mut expected = v.clone();
+        expected.sort_by_key(|&(key, _)| key);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), expected);
     }
 
     #[test]
-    fn test_ord() {
-        let n = list_from(&[]);
-        let m = list_from(&[1, 2, 3]);
-        assert!(n < m);
-        assert!(m > n);
-        assert!(n <= n);
-        assert!(n >= n);
+    fn test_sort_panic_leaves_list_usable() {
+        use std::rc::Rc;
+
+        // blow up at every point in the sort, every element has to survive
+        let v: Vec<i32> = (0..20).map(|x| x * 7 % 20).collect();
+        for limit in 0.. {
+            let tracker = Rc::new(());
+            let mut m: LinkedList<(i32, Rc<()>)> =
+                v.iter().map(|&x| (x, Rc::clone(&tracker))).collect();
+            let mut calls = 0;
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                m.sort_by(|a, b| {
+                    calls += 1;
+                    if calls > limit {
+                        panic!("bad comparator");
+                    }
+                    a.0.cmp(&b.0)
+                });
+            }));
+            check_links(&m);
+            assert_eq!(m.len(), v.len());
+            let mut elems: Vec<i32> = m.iter().map(|&(x, _)| x).collect();
+            elems.sort();
+            assert_eq!(elems, (0..20).collect::<Vec<_>>());
+
+            m.push_back((20, Rc::clone(&tracker)));
+            assert_eq!(m.len(), 21);
+            drop(m);
+            assert_eq!(Rc::strong_count(&tracker), 1);
+            if result.is_ok() {
+                break;
+            }
+        }
     }
 
     #[test]
-    fn test_ord_nan() {
-        let nan = 0.0f64 / 0.0;
-        let n = list_from(&[nan]);
-        let m = list_from(&[nan]);
-        assert!(!(n < m));
-        assert!(!(n > m));
-        assert!(!(n <= m));
-        assert!(!(n >= m));
+    fn test_node_handles() {
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let two = m.push_back_handle(2);
+        let one = m.push_front_handle(1);
+        let three = m.push_back_handle(3);
+        m.push_back(4);
+
+        assert_eq!(m.get_by_handle(&one), Some(&1));
+        assert_eq!(m.get_by_handle(&three), Some(&3));
+        *m.get_mut_by_handle(&two).unwrap() = 20;
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 20, 3, 4]);
 
-        let n = list_from(&[nan]);
-        let one = list_from(&[1.0f64]);
-        assert!(!(n < one));
-        assert!(!(n > one));
-        assert!(!(n <= one));
-        assert!(!(n >= one));
+        {
+            let mut cursor = m.cursor_at(&three).unwrap();
+            assert_eq!(cursor.current(), Some(&mut 3));
+            assert_eq!(cursor.index(), Some(2));
+            cursor.move_prev();
+            assert_eq!(cursor.index(), Some(1));
+            cursor.insert_after(10);
+        }
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 20, 10, 3, 4]);
 
-        let u = list_from(&[1.0f64, 2.0, nan]);
-        let v = list_from(&[1.0f64, 2.0, 3.0]);
-        assert!(!(u < v));
-        assert!(!(u > v));
-        assert!(!(u <= v));
-        assert!(!(u >= v));
+        assert_eq!(m.remove_by_handle(&two), Some(20));
+        check_links(&m);
+        assert_eq!(m.len(), 4);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 10, 3, 4]);
 
-        let s = list_from(&[1.0f64, 2.0, 4.0, 2.0]);
-        let t = list_from(&[1.0f64, 2.0, 3.0, 2.0]);
-        assert!(!(s < t));
-        assert!(s > one);
-        assert!(!(s <= one));
-        assert!(s >= one);
-    }
+        // the node is gone, so the handle is dead everywhere
+        assert_eq!(m.get_by_handle(&two), None);
+        assert_eq!(m.remove_by_handle(&two), None);
+        assert!(m.cursor_at(&two).is_none());
 
-    #[test]
-    fn test_debug() {
-        let list: LinkedList<i32> = (0..10).collect();
-        assert_eq!(format!("{:?}", list), "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]");
+        // as it is once popped through the normal API
+        assert_eq!(m.pop_front(), Some(1));
+        assert_eq!(m.get_by_handle(&one), None);
+
+        // handles don't work on other lists
+        let mut n: LinkedList<i32> = LinkedList::new();
+        n.push_back(3);
+        assert_eq!(n.get_by_handle(&three), None);
+        assert_eq!(n.remove_by_handle(&three), None);
+        assert_eq!(m.get_by_handle(&three), Some(&3));
+
+        // splitting a list kills all its handles, on both sides
+        let moved = m.push_back_handle(5);
+        let tail = m.split_off(2);
+        check_links(&m);
+        check_links(&tail);
+        assert_eq!(m.get_by_handle(&moved), None);
+        assert_eq!(m.get_by_handle(&three), None);
+        assert_eq!(tail.get_by_handle(&three), None);
+        assert_eq!(tail.get_by_handle(&moved), None);
+
+        // nodes that join another list leave their handles behind
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let mut other: LinkedList<i32> = LinkedList::new();
+        let ours = m.push_back_handle(0);
+        let h = other.push_back_handle(1);
+        m.append(&mut other);
+        assert_eq!(other.remove_by_handle(&h), None);
+        assert_eq!(m.remove_by_handle(&h), None);
+        assert_eq!(m.remove_by_handle(&ours), Some(0));
+        check_links(&m);
+        assert_eq!(m.len(), 1);
 
-        let list: LinkedList<&str> = vec!["just", "one", "test", "more"]
-            .iter().copied()
-            .collect();
-        assert_eq!(format!("{:?}", list), r#"["just", "one", "test", "more"]"#);
+        // moving the whole list along moves its handles too
+        let mut m: LinkedList<i32> = (0..5).collect();
+        let h = m.push_back_handle(5);
+        let mut taken = std::mem::take(&mut m);
+        taken.rotate_left(2);
+        assert_eq!(m.get_by_handle(&h), None);
+        assert_eq!(taken.remove_by_handle(&h), Some(5));
+        check_links(&taken);
+        assert_eq!(taken.iter().cloned().collect::<Vec<_>>(), &[2, 3, 4, 0, 1]);
     }
 
     #[test]
-    fn test_hashmap() {
-        // Check that HashMap works with this as a key
-
-        let list1: LinkedList<i32> = (0..10).collect();
-        let list2: LinkedList<i32> = (1..11).collect();
-        let mut map = std::collections::HashMap::new();
+    fn test_node_handles_across_moves() {
+        // truncating only kills handles to the nodes it drops
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let h = m.push_back_handle(1);
+        m.push_back(2);
+        let gone = m.push_back_handle(3);
+        m.truncate(2);
+        assert_eq!(m.get_by_handle(&gone), None);
+        assert_eq!(m.remove_by_handle(&h), Some(1));
+        check_links(&m);
 
-        assert_eq!(map.insert(list1.clone(), "list1"), None);
-        assert_eq!(map.insert(list2.clone(), "list2"), None);
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let gone = m.push_back_handle(0);
+        m.extend(1..4);
+        let h = m.push_back_handle(4);
+        m.truncate_front(2);
+        assert_eq!(m.get_by_handle(&gone), None);
+        assert_eq!(m.get_by_handle(&h), Some(&4));
+        m.drain(..1);
+        assert_eq!(m.remove_by_handle(&h), Some(4));
+        assert!(m.is_empty());
+
+        // appending keeps our handles and kills theirs, whichever way round
+        for (ours, theirs) in [(0, 5), (5, 0), (1, 5), (5, 1), (3, 3)] {
+            let mut m: LinkedList<i32> = (0..ours).collect();
+            let mut other: LinkedList<i32> = (0..theirs).collect();
+            let mine = m.push_front_handle(-1);
+            let their = other.push_back_handle(10);
+            m.append(&mut other);
+            check_links(&m);
+            assert_eq!(other.get_by_handle(&their), None);
+            assert_eq!(m.get_by_handle(&their), None);
+            assert_eq!(m.remove_by_handle(&mine), Some(-1));
+            assert_eq!(m.len(), (ours + theirs + 1) as usize);
+
+            let mut m: LinkedList<i32> = (0..ours).collect();
+            let mut other: LinkedList<i32> = (0..theirs).collect();
+            let mine = m.push_front_handle(-1);
+            let their = other.push_back_handle(10);
+            m.prepend(&mut other);
+            check_links(&m);
+            assert_eq!(m.get_by_handle(&their), None);
+            assert_eq!(m.front(), Some(&if theirs == 0 { 10 } else { 0 }));
+            assert_eq!(m.remove_by_handle(&mine), Some(-1));
+        }
 
-        assert_eq!(map.len(), 2);
+        // rotating and taking the whole list keep every handle
+        for at in 0..6 {
+            let mut m: LinkedList<i32> = LinkedList::new();
+            let handles: Vec<_> = (0..6).map(|x| m.push_back_handle(x)).collect();
+            m.rotate_left(at);
+            let m = std::mem::take(&mut m);
+            check_links(&m);
+            for (i, h) in handles.iter().enumerate() {
+                assert_eq!(m.get_by_handle(h), Some(&(i as i32)));
+            }
+        }
 
-        assert_eq!(map.get(&list1), Some(&"list1"));
-        assert_eq!(map.get(&list2), Some(&"list2"));
+        // a cursor split kills the handles into the list, unless nothing
+        // was split off
+        for at in 0..7 {
+            let mut m: LinkedList<i32> = LinkedList::new();
+            let handles: Vec<_> = (0..6).map(|x| m.push_back_handle(x)).collect();
+            let front = {
+                let mut cursor = m.cursor_mut_at(at);
+                cursor.split_before()
+            };
+            check_links(&m);
+            check_links(&front);
+            for h in &handles {
+                assert_eq!(front.get_by_handle(h), None);
+                assert_eq!(m.get_by_handle(h).is_some(), at == 0);
+            }
 
-        assert_eq!(map.remove(&list1), Some("list1"));
-        assert_eq!(map.remove(&list2), Some("list2"));
+            let mut m: LinkedList<i32> = LinkedList::new();
+            let handles: Vec<_> = (0..6).map(|x| m.push_back_handle(x)).collect();
+            let back = {
+                let mut cursor = m.cursor_mut_at(at);
+                cursor.split_after()
+            };
+            for h in &handles {
+                assert_eq!(back.get_by_handle(h), None);
+                assert_eq!(m.get_by_handle(h).is_some(), at == 5);
+            }
+        }
 
-        assert!(map.is_empty());
+        // moving a range only kills the handles to what moved
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let handles: Vec<_> = (0..6).map(|x| m.push_back_handle(x)).collect();
+        let mut other: LinkedList<i32> = LinkedList::new();
+        other.extend(10..12);
+        let theirs = other.push_back_handle(12);
+        {
+            let mut cursor = m.cursor_at(&handles[1]).unwrap();
+            let mut dest = other.cursor_mut();
+            dest.move_next();
+            cursor.move_range_to(3, &mut dest);
+        }
+        check_links(&m);
+        check_links(&other);
+        assert_eq!(other.iter().cloned().collect::<Vec<_>>(), &[1, 2, 3, 10, 11, 12]);
+        assert_eq!(other.get_by_handle(&handles[2]), None);
+        assert_eq!(m.get_by_handle(&handles[2]), None);
+        assert_eq!(other.remove_by_handle(&theirs), Some(12));
+        assert_eq!(m.remove_by_handle(&handles[4]), Some(4));
+        assert_eq!(m.remove_by_handle(&handles[0]), Some(0));
+
+        // partition keeps the handles to what didn't match, chunks kill all
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let handles: Vec<_> = (0..6).map(|x| m.push_back_handle(x)).collect();
+        let (evens, odds) = m.partition(|x| x % 2 == 0);
+        assert_eq!(evens.get_by_handle(&handles[4]), None);
+        assert_eq!(odds.get_by_handle(&handles[3]), Some(&3));
+        assert_eq!(odds.get_by_handle(&handles[4]), None);
+        let chunks = odds.split_into_chunks(2);
+        assert_eq!(chunks[0].get_by_handle(&handles[1]), None);
+        assert_eq!(chunks[1].get_by_handle(&handles[5]), None);
+
+        // handles killed by a split stay dead once new ones sweep them out,
+        // even if their node comes back
+        let mut m: LinkedList<i32> = LinkedList::new();
+        let old: Vec<_> = (0..6).map(|x| m.push_back_handle(x)).collect();
+        let mut back = m.split_off(1);
+        let new: Vec<_> = (10..13).map(|x| m.push_back_handle(x)).collect();
+        m.append(&mut back);
+        check_links(&m);
+        for h in &old {
+            assert_eq!(m.get_by_handle(h), None);
+        }
+        for (h, x) in new.iter().zip(10..) {
+            assert_eq!(m.remove_by_handle(h), Some(x));
+        }
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 1, 2, 3, 4, 5]);
     }
+
     #[test]
-    fn test_cursor_move_peek() {
+    fn test_cursor_mut_edit_elements() {
         let mut m: LinkedList<u32> = LinkedList::new();
-        m.extend([1, 2, 3, 4, 5, 6]);
+
+        // on an empty list everything lands around the ghost
         let mut cursor = m.cursor_mut();
-        cursor.move_next();
-        assert_eq!(cursor.current(), Some(&mut 1));
-        assert_eq!(cursor.peek_next(), Some(&mut 2));
-        assert_eq!(cursor.peek_prev(), None);
-        assert_eq!(cursor.index(), Some(0));
-        cursor.move_prev();
-        assert_eq!(cursor.current(), None);
-        assert_eq!(cursor.peek_next(), Some(&mut 1));
-        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.remove_current(), None);
+        assert!(cursor.remove_current_as_list().is_none());
+        cursor.insert_before(2);
+        cursor.insert_after(1);
+        cursor.insert_before(3);
         assert_eq!(cursor.index(), None);
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 2, 3]);
+
+        let mut cursor = m.cursor_mut();
         cursor.move_next();
-        cursor.move_next();
-        assert_eq!(cursor.current(), Some(&mut 2));
-        assert_eq!(cursor.peek_next(), Some(&mut 3));
-        assert_eq!(cursor.peek_prev(), Some(&mut 1));
+        cursor.insert_before(0);
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        cursor.insert_after(10);
         assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.replace_current(11), 1);
+        assert_eq!(cursor.current(), Some(&mut 11));
+        check_links(&m);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 11, 10, 2, 3]);
 
         let mut cursor = m.cursor_mut();
         cursor.move_prev();
-        assert_eq!(cursor.current(), Some(&mut 6));
-        assert_eq!(cursor.peek_next(), None);
-        assert_eq!(cursor.peek_prev(), Some(&mut 5));
-        assert_eq!(cursor.index(), Some(5));
-        cursor.move_next();
-        assert_eq!(cursor.current(), None);
-        assert_eq!(cursor.peek_next(), Some(&mut 1));
-        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.remove_current(), Some(3));
         assert_eq!(cursor.index(), None);
-        cursor.move_prev();
-        cursor.move_prev();
-        assert_eq!(cursor.current(), Some(&mut 5));
-        assert_eq!(cursor.peek_next(), Some(&mut 6));
-        assert_eq!(cursor.peek_prev(), Some(&mut 4));
-        assert_eq!(cursor.index(), Some(4));
+        cursor.move_next();
+        let removed = cursor.remove_current_as_list().unwrap();
+        assert_eq!(removed.len(), 1);
+        check_links(&removed);
+        assert_eq!(removed.front(), Some(&0));
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 11));
+        check_links(&m);
+        assert_eq!(m.len(), 3);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[11, 10, 2]);
+
+        let mut cursor = m.cursor_mut();
+        while cursor.peek_next().is_some() {
+            cursor.move_next();
+            cursor.remove_current();
+            cursor.move_prev();
+        }
+        assert!(m.is_empty());
+        check_links(&m);
     }
 
     #[test]
-    fn test_cursor_mut_insert() {
-        let mut m: LinkedList<u32> = LinkedList::new();
-        m.extend([1, 2, 3, 4, 5, 6]);
+    fn test_cursor_mut_remove_range() {
+        let mut m: LinkedList<i32> = (0..8).collect();
         let mut cursor = m.cursor_mut();
+        assert!(cursor.remove_range(0).is_empty());
         cursor.move_next();
-        cursor.splice_before(Some(7).into_iter().collect());
-        cursor.splice_after(Some(8).into_iter().collect());
-        // check_links(&m);
-        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[7, 1, 8, 2, 3, 4, 5, 6]);
-        let mut cursor = m.cursor_mut();
         cursor.move_next();
-        cursor.move_prev();
-        cursor.splice_before(Some(9).into_iter().collect());
-        cursor.splice_after(Some(10).into_iter().collect());
+        cursor.move_next();
+        let range = cursor.remove_range(3);
+        assert_eq!(cursor.current(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(2));
+        check_links(&range);
+        assert_eq!(range.iter().cloned().collect::<Vec<_>>(), &[2, 3, 4]);
         check_links(&m);
-        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[10, 7, 1, 8, 2, 3, 4, 5, 6, 9]);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 1, 5, 6, 7]);
 
-        /* remove_current not impl'd
+        // all the way to the back leaves us on the ghost
         let mut cursor = m.cursor_mut();
         cursor.move_next();
-        cursor.move_prev();
-        assert_eq!(cursor.remove_current(), None);
-        cursor.move_next();
         cursor.move_next();
-        assert_eq!(cursor.remove_current(), Some(7));
-        cursor.move_prev();
-        cursor.move_prev();
-        cursor.move_prev();
-        assert_eq!(cursor.remove_current(), Some(9));
         cursor.move_next();
-        assert_eq!(cursor.remove_current(), Some(10));
+        let range = cursor.remove_range(3);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(range.iter().cloned().collect::<Vec<_>>(), &[5, 6, 7]);
         check_links(&m);
-        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[1, 8, 2, 3, 4, 5, 6]);
-        */
-
-        let mut m: LinkedList<u32> = LinkedList::new();
-        m.extend([1, 8, 2, 3, 4, 5, 6]);
 
+        // and the whole list from the front
         let mut cursor = m.cursor_mut();
         cursor.move_next();
-        let mut p: LinkedList<u32> = LinkedList::new();
-        p.extend([100, 101, 102, 103]);
-        let mut q: LinkedList<u32> = LinkedList::new();
-        q.extend([200, 201, 202, 203]);
-        cursor.splice_after(p);
-        cursor.splice_before(q);
+        let range = cursor.remove_range(2);
+        assert_eq!(range.iter().cloned().collect::<Vec<_>>(), &[0, 1]);
         check_links(&m);
-        assert_eq!(
-            m.iter().cloned().collect::<Vec<_>>(),
-            &[200, 201, 202, 203, 1, 100, 101, 102, 103, 8, 2, 3, 4, 5, 6]
-        );
-        let mut cursor = m.cursor_mut();
-        cursor.move_next();
-        cursor.move_prev();
-        let tmp = cursor.split_before();
-        assert_eq!(m.into_iter().collect::<Vec<_>>(), &[]);
-        m = tmp;
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "range extends past the back of the list")]
+    fn test_cursor_mut_remove_range_too_long() {
+        let mut m: LinkedList<i32> = (0..4).collect();
         let mut cursor = m.cursor_mut();
         cursor.move_next();
         cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
-        cursor.move_next();
-        let tmp = cursor.split_after();
-        assert_eq!(tmp.into_iter().collect::<Vec<_>>(), &[102, 103, 8, 2, 3, 4, 5, 6]);
+        cursor.remove_range(4);
+    }
+
+    #[test]
+    fn test_cursor_mut_move_range_to() {
+        let mut m: LinkedList<i32> = (0..6).collect();
+        let mut n: LinkedList<i32> = (10..13).collect();
+        {
+            let mut from = m.cursor_mut();
+            let mut to = n.cursor_mut();
+            from.move_next();
+            from.move_next();
+            from.move_next();
+            to.move_next();
+            from.move_range_to(3, &mut to);
+            assert_eq!(from.current(), Some(&mut 5));
+            assert_eq!(to.current(), Some(&mut 10));
+            assert_eq!(to.index(), Some(3));
+
+            // on the ghost the range goes to the back
+            for _ in 0..4 {
+                to.move_prev();
+            }
+            assert_eq!(to.current(), None);
+            from.move_prev();
+            from.move_range_to(1, &mut to);
+            assert_eq!(to.index(), None);
+        }
         check_links(&m);
-        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[200, 201, 202, 203, 1, 100, 101]);
+        check_links(&n);
+        assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[0, 5]);
+        assert_eq!(n.iter().cloned().collect::<Vec<_>>(), &[2, 3, 4, 10, 11, 12, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cursor_mut_replace_ghost() {
+        let mut m: LinkedList<u32> = LinkedList::new();
+        m.push_back(1);
+        m.cursor_mut().replace_current(2);
+    }
+
+    fn check_links<T: Eq + std::fmt::Debug>(list: &LinkedList<T>) {
+        let from_front: Vec<_> = list.iter().collect();
+        let from_back: Vec<_> = list.iter().rev().collect();
+        let re_reved: Vec<_> = from_back.into_iter().rev().collect();
+
+        assert_eq!(from_front, re_reved);
+
+        // walk the raw pointers too, so a dangling `front`/`back` between
+        // nodes is caught even when `len` happens to hide it from `iter`
+        unsafe {
+            let mut len = 0;
+            let mut prev = None;
+            let mut cur = list.front;
+            while let Some(node) = cur {
+                assert_eq!((*node.as_ptr()).front, prev);
+                prev = cur;
+                cur = (*node.as_ptr()).back;
+                len += 1;
+            }
+            assert_eq!(list.back, prev);
+            assert_eq!(list.len, len);
+        }
+    }
+
+    /// Tiny xorshift PRNG so the differential test is reproducible and
+    /// doesn't need any dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
     }
 
-    fn check_links<T>(_list: &LinkedList<T>) {
-        // would be good to do this!
+    fn check_against<T: Eq + std::fmt::Debug>(
+        list: &LinkedList<T>,
+        model: &std::collections::LinkedList<T>,
+    ) {
+        check_links(list);
+        assert_eq!(list.len(), model.len());
+        assert!(list.iter().eq(model.iter()));
+        assert!(list.iter().rev().eq(model.iter().rev()));
     }
 
+    #[test]
+    fn test_cursor_differential() {
+        use std::collections::LinkedList as StdList;
+
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        let mut list = LinkedList::new();
+        let mut model = StdList::new();
+        let mut next = 0u32;
+
+        for _ in 0..5000 {
+            let op = rng.below(12);
+            match op {
+                0 => { list.push_front(next); model.push_front(next); next += 1; }
+                1 => { list.push_back(next); model.push_back(next); next += 1; }
+                2 => assert_eq!(list.pop_front(), model.pop_front()),
+                3 => assert_eq!(list.pop_back(), model.pop_back()),
+                _ => {
+                    // park a cursor somewhere, `len` meaning the ghost
+                    let len = model.len();
+                    let pos = rng.below(len + 1);
+                    let mut cursor = list.cursor_mut();
+                    if pos < len / 2 {
+                        for _ in 0..=pos { cursor.move_next(); }
+                    } else {
+                        for _ in pos..len { cursor.move_prev(); }
+                    }
+                    let on_ghost = pos == len;
+                    assert_eq!(cursor.index(), if on_ghost { None } else { Some(pos) });
+                    assert_eq!(cursor.current().copied(), model.iter().nth(pos).copied());
+
+                    let input_len = rng.below(4);
+                    let input: Vec<u32> = (next..next + input_len as u32).collect();
+                    next += input_len as u32;
+
+                    match op {
+                        4 => {
+                            let out = cursor.split_before();
+                            let expected_idx = if on_ghost { None } else { Some(0) };
+                            assert_eq!(cursor.index(), expected_idx);
+                            let at = if on_ghost { len } else { pos };
+                            let tail = model.split_off(at);
+                            let expected = std::mem::replace(&mut model, tail);
+                            check_against(&out, &expected);
+                        }
+                        5 => {
+                            let out = cursor.split_after();
+                            let expected_idx = if on_ghost { None } else { Some(pos) };
+                            assert_eq!(cursor.index(), expected_idx);
+                            let expected = if on_ghost {
+                                std::mem::take(&mut model)
+                            } else {
+                                model.split_off(pos + 1)
+                            };
+                            check_against(&out, &expected);
+                        }
+                        6 => {
+                            cursor.insert_before(next);
+                            let expected_idx = if on_ghost { None } else { Some(pos + 1) };
+                            assert_eq!(cursor.index(), expected_idx);
+                            let mut tail = model.split_off(pos);
+                            model.push_back(next);
+                            model.append(&mut tail);
+                            next += 1;
+                        }
+                        7 => {
+                            cursor.insert_after(next);
+                            assert_eq!(cursor.index(), if on_ghost { None } else { Some(pos) });
+                            let at = if on_ghost { 0 } else { pos + 1 };
+                            let mut tail = model.split_off(at);
+                            model.push_back(next);
+                            model.append(&mut tail);
+                            next += 1;
+                        }
+                        8 => {
+                            let removed = cursor.remove_current();
+                            let expected_idx = if pos + 1 < len { Some(pos) } else { None };
+                            assert_eq!(cursor.index(), expected_idx);
+                            if on_ghost {
+                                assert_eq!(removed, None);
+                            } else {
+                                let mut tail = model.split_off(pos);
+                                assert_eq!(removed, tail.pop_front());
+                                model.append(&mut tail);
+                            }
+                        }
+                        9 => {
+                            if !on_ghost {
+                                let old = cursor.replace_current(next);
+                                assert_eq!(Some(&old), model.iter().nth(pos));
+                                *model.iter_mut().nth(pos).unwrap() = next;
+                                next += 1;
+                            }
+                        }
+                        10 => {
+                            cursor.splice_before(input.iter().copied().collect());
+                            let expected_idx = if on_ghost { None } else { Some(pos + input_len) };
+                            assert_eq!(cursor.index(), expected_idx);
+                            let mut tail = model.split_off(pos);
+                            model.extend(input);
+                            model.append(&mut tail);
+                        }
+                        _ => {
+                            cursor.splice_after(input.iter().copied().collect());
+                            let expected_idx = if on_ghost { None } else { Some(pos) };
+                            assert_eq!(cursor.index(), expected_idx);
+                            let at = if on_ghost { 0 } else { pos + 1 };
+                            let mut tail = model.split_off(at);
+                            model.extend(input);
+                            model.append(&mut tail);
+                        }
+                    }
+                }
+            }
+            check_against(&list, &model);
+        }
+    }
 }
@@ -93,6 +93,17 @@ mod test {
         assert_eq!(list.head(), None);
     }
 
+    #[test]
+    fn linked_list_macro() {
+        let list = linked_list![third => 1, 2, 3];
+        assert_eq!(list.head(), Some(&3));
+        assert_eq!(list.tail().head(), Some(&2));
+
+        let list = linked_list![third => 7; 2];
+        assert_eq!(list.iter().count(), 2);
+        assert_eq!(list.head(), Some(&7));
+    }
+
     #[test]
     fn iter() {
         let list = List::new().prepend(1).prepend(2).prepend(3);